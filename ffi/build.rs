@@ -0,0 +1,15 @@
+// Generates the C header into OUT_DIR. The copy in include/ is the one C code builds against;
+// the tests compare the two, so a change to the API that is not reflected there fails.
+
+use std::{env, path::PathBuf};
+
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    cbindgen::generate(&crate_dir)
+        .expect("could not generate the C header")
+        .write_to_file(out_dir.join("spinning_top.h"));
+}
@@ -0,0 +1,66 @@
+/*
+ * Spins a unit cube on its vertex for one second and prints its state and how well energy
+ * was conserved. Fails when any call does, or when the energy drifts by more than 1e-6.
+ */
+
+#include <math.h>
+#include <stdio.h>
+
+#include "spinning_top.h"
+
+#define CHECK(call)                                                     \
+    do {                                                                \
+        SpinningTopStatus status = (call);                              \
+        if (status != SPINNING_TOP_STATUS_OK) {                         \
+            fprintf(stderr, "%s failed with status %d\n", #call, status); \
+            return 1;                                                   \
+        }                                                               \
+    } while (0)
+
+int main(void) {
+    SpinningTop *top = spinning_top_new_cube(1.0, 1.0);
+    if (top == NULL) {
+        fprintf(stderr, "could not create the top\n");
+        return 1;
+    }
+
+    /* Tilted 0.1 rad about x, spinning at 20 rad/s about the body y axis. */
+    const double q[4] = {cos(0.05), sin(0.05), 0.0, 0.0};
+    const double omega[3] = {0.0, 20.0, 0.0};
+    CHECK(spinning_top_set_state(top, q, omega));
+    CHECK(spinning_top_set_integrator(top, SPINNING_TOP_INTEGRATOR_RUNGE_KUTTA4, 0.001));
+    CHECK(spinning_top_step(top, 1000));
+
+    double t;
+    double q_end[4];
+    double omega_end[3];
+    SpinningTopDiagnostics diagnostics;
+    CHECK(spinning_top_time(top, &t));
+    CHECK(spinning_top_quaternion(top, q_end));
+    CHECK(spinning_top_angular_velocity(top, omega_end));
+    CHECK(spinning_top_diagnostics(top, &diagnostics));
+
+    printf("t = %.3f s\n", t);
+    printf("q = (%.6f, %.6f, %.6f, %.6f)\n", q_end[0], q_end[1], q_end[2], q_end[3]);
+    printf("omega = (%.6f, %.6f, %.6f)\n", omega_end[0], omega_end[1], omega_end[2]);
+    printf("energy = %.9f, drift %.3e\n", diagnostics.total_energy, diagnostics.energy_drift);
+
+    if (spinning_top_step(NULL, 1) != SPINNING_TOP_STATUS_NULL_POINTER) {
+        fprintf(stderr, "a null handle was accepted\n");
+        return 1;
+    }
+    if (spinning_top_set_integrator(top, 7, 0.001) != SPINNING_TOP_STATUS_INVALID_ARGUMENT ||
+        spinning_top_set_integrator(top, SPINNING_TOP_INTEGRATOR_EULER, INFINITY) !=
+            SPINNING_TOP_STATUS_INVALID_ARGUMENT) {
+        fprintf(stderr, "an invalid integrator or step was accepted\n");
+        return 1;
+    }
+    if (spinning_top_new_cube(INFINITY, 1.0) != NULL || spinning_top_new_cube(1.0, NAN) != NULL) {
+        fprintf(stderr, "a non-finite cube was accepted\n");
+        return 1;
+    }
+
+    spinning_top_free(top);
+
+    return fabs(t - 1.0) < 1e-9 && fabs(diagnostics.energy_drift) < 1e-6 ? 0 : 1;
+}
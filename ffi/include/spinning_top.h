@@ -0,0 +1,156 @@
+/* Heavy spinning top simulator, C API. Generated by cbindgen from src/lib.rs, do not edit. */
+
+#ifndef SPINNING_TOP_H
+#define SPINNING_TOP_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum SpinningTopStatus {
+  SPINNING_TOP_STATUS_OK = 0,
+  SPINNING_TOP_STATUS_NULL_POINTER = 1,
+  SPINNING_TOP_STATUS_INVALID_ARGUMENT = 2,
+} SpinningTopStatus;
+
+/**
+ * Values of the `integrator` argument of `spinning_top_set_integrator`. It is passed as an
+ * integer, so a value outside these is reported rather than undefined.
+ */
+typedef enum SpinningTopIntegrator {
+  SPINNING_TOP_INTEGRATOR_EULER = 0,
+  SPINNING_TOP_INTEGRATOR_RUNGE_KUTTA4 = 1,
+  SPINNING_TOP_INTEGRATOR_DORMAND_PRINCE = 2,
+  SPINNING_TOP_INTEGRATOR_CROUCH_GROSSMAN = 3,
+} SpinningTopIntegrator;
+
+/**
+ * A heavy top and its solver, created by `spinning_top_new_cube` and released with
+ * `spinning_top_free`.
+ */
+typedef struct SpinningTop SpinningTop;
+
+/**
+ * Conserved quantities of the current state. The drifts are relative to their values when
+ * the state or the integrator was last set, or when gravity was last toggled; setting the
+ * integrator restarts the solver from the current state, so earlier drift is not carried over.
+ */
+typedef struct SpinningTopDiagnostics {
+  double kinetic_energy;
+  double potential_energy;
+  double total_energy;
+  double vertical_angular_momentum;
+  double angular_momentum_norm;
+  double quaternion_norm_error;
+  double energy_drift;
+  double vertical_angular_momentum_drift;
+  double angular_momentum_norm_drift;
+} SpinningTopDiagnostics;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * A cube of edge `size` and `density` standing on a vertex, in double precision with RK4 at
+ * a fixed step of 0.001 s, gravity on and the viewer's default initial state. Returns null
+ * when the size or density is not positive and finite.
+ */
+struct SpinningTop *spinning_top_new_cube(double size, double density);
+
+/**
+ * Releases a top. Null is ignored.
+ *
+ * # Safety
+ *
+ * `top` must be null or come from `spinning_top_new_cube` and not have been freed.
+ */
+void spinning_top_free(struct SpinningTop *top);
+
+/**
+ * Switches the integrator, one of `SpinningTopIntegrator`, and its fixed step `h`, and
+ * restarts the solver from the current state; the drifts are measured from there on.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle.
+ */
+enum SpinningTopStatus spinning_top_set_integrator(struct SpinningTop *top,
+                                                   uint32_t integrator,
+                                                   double h);
+
+/**
+ * Turns gravity on or off without touching the state.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle.
+ */
+enum SpinningTopStatus spinning_top_set_gravity(struct SpinningTop *top, bool gravity);
+
+/**
+ * Restarts the run at t = 0 from the orientation `q`, four doubles w, i, j, k normalised
+ * here, and the angular velocity `omega`, three doubles in the body frame.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle, `q` and `omega` null or valid for reading four and
+ * three doubles.
+ */
+enum SpinningTopStatus spinning_top_set_state(struct SpinningTop *top,
+                                              const double *q,
+                                              const double *omega);
+
+/**
+ * Advances `steps` steps of the fixed grid.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle.
+ */
+enum SpinningTopStatus spinning_top_step(struct SpinningTop *top, uint64_t steps);
+
+/**
+ * Simulated time in seconds.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle, `t` null or valid for writing a double.
+ */
+enum SpinningTopStatus spinning_top_time(const struct SpinningTop *top, double *t);
+
+/**
+ * Writes the orientation to `q` as four doubles w, i, j, k.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle, `q` null or valid for writing four doubles.
+ */
+enum SpinningTopStatus spinning_top_quaternion(const struct SpinningTop *top, double *q);
+
+/**
+ * Writes the angular velocity in the body frame to `omega` as three doubles.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle, `omega` null or valid for writing three doubles.
+ */
+enum SpinningTopStatus spinning_top_angular_velocity(const struct SpinningTop *top, double *omega);
+
+/**
+ * Fills `diagnostics` for the current state.
+ *
+ * # Safety
+ *
+ * `top` must be null or a live handle, `diagnostics` null or valid for writing.
+ */
+enum SpinningTopStatus spinning_top_diagnostics(const struct SpinningTop *top,
+                                                struct SpinningTopDiagnostics *diagnostics);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SPINNING_TOP_H */
@@ -0,0 +1,50 @@
+// Builds examples/spin.c against the static library and the committed header, and runs it.
+
+use std::{env, fs, path::PathBuf, process::Command};
+
+#[test]
+fn committed_header_is_current() {
+    let committed = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("include/spinning_top.h");
+    let generated = PathBuf::from(env!("OUT_DIR")).join("spinning_top.h");
+
+    assert!(
+        fs::read_to_string(&committed).unwrap() == fs::read_to_string(&generated).unwrap(),
+        "{} is out of date, copy {} over it",
+        committed.display(),
+        generated.display()
+    );
+}
+
+#[test]
+fn c_example_runs() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // The library is built next to the test binary, in target/<profile>/deps. The copy one
+    // directory up is only refreshed by `cargo build`, so it may predate the test.
+    let library = env::current_exe()
+        .unwrap()
+        .with_file_name("libspinning_top_ffi.a");
+    assert!(library.exists(), "{} was not built", library.display());
+
+    let executable = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("spin");
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&compiler)
+        .arg(manifest_dir.join("examples/spin.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(&library)
+        .args(["-lm", "-lpthread", "-ldl", "-o"])
+        .arg(&executable)
+        .status()
+        .unwrap_or_else(|error| panic!("could not run {}: {}", compiler, error));
+    assert!(status.success(), "compiling the C example failed");
+
+    let output = Command::new(&executable).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "the C example failed:\n{}{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.starts_with("t = 1.000 s"), "{}", stdout);
+}
@@ -0,0 +1,332 @@
+use numpy::{ndarray::Array2, IntoPyArray, PyArray1, PyArray2, PyReadonlyArray2};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use spinning_top::{
+    BodyDefinition, Config, InitialConditions, IntegratorKind, Orientation, Pivot, Precision,
+    PrincipalInertia, Real, Shape, SimulationParameters, TopDynamics, VelocityFrame,
+};
+
+// Each sample takes 64 bytes across the three arrays, so this caps a run at about 640 MB.
+const MAX_SAMPLES: f64 = 1e7;
+
+type Trajectory<'py> = (
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+);
+
+/// A heavy top with its initial state and solver settings. `run` always starts from the initial
+/// state, so the same settings give the same arrays, and the same as the viewer gives for them.
+#[pyclass(module = "spinning_top")]
+struct Top {
+    parameters: SimulationParameters,
+}
+
+#[pymethods]
+impl Top {
+    /// A cube of edge `size` and `density` on the viewer's default pivot.
+    #[staticmethod]
+    #[pyo3(signature = (size = 1.0, density = 1.0))]
+    fn cube(size: f64, density: f64) -> PyResult<Self> {
+        let body = BodyDefinition::Shape {
+            shape: Shape::Cube { size },
+            density,
+            pivot: Pivot::Natural,
+            unbalance: [0.0; 3],
+        };
+        if !body.is_physical() {
+            return Err(PyValueError::new_err(
+                "size and density must be positive and finite",
+            ));
+        }
+
+        Ok(Self::new(body))
+    }
+
+    /// A body given by its mass, principal moments of inertia about the pivot, the orientation
+    /// of those principal axes as roll, pitch and yaw angles in radians, and the centre of mass
+    /// relative to the pivot.
+    #[staticmethod]
+    #[pyo3(signature = (mass, moments, center_of_mass, axes = [0.0; 3]))]
+    fn from_inertia(
+        mass: f64,
+        moments: [f64; 3],
+        center_of_mass: [f64; 3],
+        axes: [f64; 3],
+    ) -> PyResult<Self> {
+        let inertia = PrincipalInertia {
+            mass,
+            moments,
+            axes,
+            center_of_mass,
+        };
+        if !inertia.is_physical() {
+            return Err(PyValueError::new_err(
+                "the mass and moments must be positive and satisfy the triangle inequality",
+            ));
+        }
+
+        Ok(Self::new(BodyDefinition::Inertia(inertia)))
+    }
+
+    /// Body, state and solver settings of a configuration saved from the viewer.
+    #[staticmethod]
+    fn from_config(path: &str) -> PyResult<Self> {
+        let config =
+            Config::load(path).map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+        Ok(Self {
+            parameters: config.simulation_parameters(),
+        })
+    }
+
+    /// Orientation as a quaternion (w, i, j, k), normalised here, and ω in the body frame.
+    fn set_state(&mut self, q: [f64; 4], omega: [f64; 3]) -> PyResult<()> {
+        if !q.iter().chain(&omega).all(|x| x.is_finite()) {
+            return Err(PyValueError::new_err(
+                "the quaternion and angular velocity must be finite",
+            ));
+        }
+        if q.iter().map(|x| x * x).sum::<f64>() == 0.0 {
+            return Err(PyValueError::new_err("the quaternion must not be zero"));
+        }
+
+        self.parameters.initial = InitialConditions {
+            orientation: Orientation::Quaternion(q),
+            angular_velocity: omega,
+            frame: VelocityFrame::Body,
+        };
+
+        Ok(())
+    }
+
+    /// One of "euler", "rk4", "dormand-prince" or "crouch-grossman".
+    #[getter]
+    fn integrator(&self) -> &'static str {
+        match self.parameters.integrator {
+            IntegratorKind::Euler => "euler",
+            IntegratorKind::RungeKutta4 => "rk4",
+            IntegratorKind::DormandPrince => "dormand-prince",
+            IntegratorKind::CrouchGrossman => "crouch-grossman",
+        }
+    }
+
+    #[setter]
+    fn set_integrator(&mut self, name: &str) -> PyResult<()> {
+        self.parameters.integrator = match name {
+            "euler" => IntegratorKind::Euler,
+            "rk4" => IntegratorKind::RungeKutta4,
+            "dormand-prince" => IntegratorKind::DormandPrince,
+            "crouch-grossman" => IntegratorKind::CrouchGrossman,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown integrator {:?}",
+                    name
+                )))
+            }
+        };
+
+        Ok(())
+    }
+
+    /// "f32" or "f64", the floating point type the solver works in. New tops use f64, the viewer
+    /// starts in f32.
+    #[getter]
+    fn precision(&self) -> String {
+        self.parameters.precision.to_string()
+    }
+
+    #[setter]
+    fn set_precision(&mut self, name: &str) -> PyResult<()> {
+        self.parameters.precision = Precision::ALL
+            .into_iter()
+            .find(|precision| precision.to_string() == name)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown precision {:?}", name)))?;
+
+        Ok(())
+    }
+
+    #[getter]
+    fn gravity(&self) -> bool {
+        self.parameters.gravity
+    }
+
+    #[setter]
+    fn set_gravity(&mut self, gravity: bool) {
+        self.parameters.gravity = gravity;
+    }
+
+    /// Integrates with step `h` up to `t_end` and returns the time (n,), the quaternions
+    /// (n, 4) as w, i, j, k and the body angular velocities (n, 3), initial state included.
+    fn run<'py>(&self, py: Python<'py>, t_end: f64, h: f64) -> PyResult<Trajectory<'py>> {
+        if !(h.is_finite() && h > 0.0 && t_end.is_finite() && t_end >= 0.0) {
+            return Err(PyValueError::new_err(
+                "h must be positive and t_end not negative",
+            ));
+        }
+        if (t_end / h).round() >= MAX_SAMPLES {
+            return Err(PyValueError::new_err(format!(
+                "t_end / h must be below {}, increase h or split the run",
+                MAX_SAMPLES
+            )));
+        }
+
+        let parameters = SimulationParameters {
+            integration_step: h,
+            step_controller: None,
+            end_time: Some(t_end),
+            ..self.parameters.clone()
+        };
+        let (t, q, w) = py.detach(|| match parameters.precision {
+            Precision::Single => run_with::<f32>(&parameters, t_end),
+            Precision::Double => run_with::<f64>(&parameters, t_end),
+        });
+
+        Ok((
+            t.into_pyarray(py),
+            Array2::from_shape_vec((q.len() / 4, 4), q)
+                .unwrap()
+                .into_pyarray(py),
+            Array2::from_shape_vec((w.len() / 3, 3), w)
+                .unwrap()
+                .into_pyarray(py),
+        ))
+    }
+
+    /// Total energy of each of the states (n, 4) and (n, 3) as `run` returns them, with the
+    /// top's gravity setting.
+    fn energy<'py>(
+        &self,
+        py: Python<'py>,
+        q: PyReadonlyArray2<'py, f64>,
+        omega: PyReadonlyArray2<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let (q, omega) = (q.as_array(), omega.as_array());
+        if q.ncols() != 4 || omega.ncols() != 3 || q.nrows() != omega.nrows() {
+            return Err(PyValueError::new_err(
+                "q and omega must have shapes (n, 4) and (n, 3)",
+            ));
+        }
+
+        let mut dynamics = TopDynamics::new(&self.parameters.build_body::<f64>());
+        dynamics.set_gravity(self.parameters.gravity);
+        let energies: Vec<f64> = q
+            .rows()
+            .into_iter()
+            .zip(omega.rows())
+            .map(|(q, omega)| {
+                energy(
+                    &dynamics,
+                    [q[0], q[1], q[2], q[3]],
+                    [omega[0], omega[1], omega[2]],
+                )
+            })
+            .collect();
+
+        Ok(energies.into_pyarray(py))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Top({}, integrator={:?}, precision={:?}, gravity={})",
+            self.parameters.body,
+            self.integrator(),
+            self.precision(),
+            self.parameters.gravity
+        )
+    }
+}
+
+impl Top {
+    fn new(body: BodyDefinition) -> Self {
+        Self {
+            parameters: SimulationParameters {
+                body,
+                precision: Precision::Double,
+                ..Config::default().simulation_parameters()
+            },
+        }
+    }
+}
+
+// Steps on the same grid t = n·h as the viewer, so the samples line up with its history.
+fn run_with<T: Real>(
+    parameters: &SimulationParameters,
+    t_end: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut simulator = parameters.build_simulator::<T>();
+    let steps = (t_end / parameters.integration_step).round() as u64;
+
+    let mut t = Vec::with_capacity(steps as usize + 1);
+    let mut q = Vec::with_capacity(4 * (steps as usize + 1));
+    let mut w = Vec::with_capacity(3 * (steps as usize + 1));
+    loop {
+        let state = simulator.state().cast::<f64>();
+        let quaternion = state.q.quaternion();
+        t.push(state.t);
+        q.extend([quaternion.w, quaternion.i, quaternion.j, quaternion.k]);
+        w.extend(state.w.iter());
+
+        if simulator.steps() >= steps {
+            break;
+        }
+        simulator.advance_step();
+    }
+
+    (t, q, w)
+}
+
+fn energy(dynamics: &TopDynamics<f64>, q: [f64; 4], omega: [f64; 3]) -> f64 {
+    let state = InitialConditions {
+        orientation: Orientation::Quaternion(q),
+        angular_velocity: omega,
+        frame: VelocityFrame::Body,
+    }
+    .state();
+
+    dynamics.diagnostics(&state, 1.0).total_energy()
+}
+
+#[pymodule]
+#[pyo3(name = "spinning_top")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Top>()?;
+    module.add(
+        "__doc__",
+        "Heavy spinning top simulator, the solver of the spinning_top viewer.",
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use spinning_top::{SimulationParameters, TopDynamics};
+
+    use super::{energy, run_with, Top};
+
+    // The arrays `run` wraps, checked without an interpreter.
+    #[test]
+    fn run_gives_one_sample_per_step_and_conserves_energy() {
+        let mut top = Top::cube(1.0, 1.0).unwrap();
+        top.set_state([0.9987, 0.05, 0.0, 0.0], [0.0, 20.0, 0.0])
+            .unwrap();
+        let parameters = SimulationParameters {
+            integration_step: 1e-3,
+            ..top.parameters.clone()
+        };
+
+        let (t, q, w) = run_with::<f64>(&parameters, 1.0);
+        assert_eq!((t.len(), q.len(), w.len()), (1001, 4 * 1001, 3 * 1001));
+        assert!((t[1000] - 1.0).abs() < 1e-12);
+
+        let dynamics = TopDynamics::new(&parameters.build_body::<f64>());
+        let energy_at = |n: usize| {
+            energy(
+                &dynamics,
+                q[4 * n..4 * n + 4].try_into().unwrap(),
+                w[3 * n..3 * n + 3].try_into().unwrap(),
+            )
+        };
+        assert!(((energy_at(1000) - energy_at(0)) / energy_at(0)).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,44 @@
+# Smoke tests of the built module: `maturin develop` in python/, then
+# `python -m unittest discover tests` there.
+
+import math
+import unittest
+
+import numpy as np
+
+from spinning_top import Top
+
+
+class RunTest(unittest.TestCase):
+    def setUp(self):
+        self.top = Top.cube(1.0, 1.0)
+        self.top.set_state([math.cos(0.05), math.sin(0.05), 0.0, 0.0], [0.0, 20.0, 0.0])
+
+    def test_shapes(self):
+        t, q, w = self.top.run(1.0, 1e-3)
+
+        self.assertEqual(t.shape, (1001,))
+        self.assertEqual(q.shape, (1001, 4))
+        self.assertEqual(w.shape, (1001, 3))
+        self.assertAlmostEqual(t[-1], 1.0, places=12)
+        np.testing.assert_allclose(np.linalg.norm(q, axis=1), 1.0, atol=1e-12)
+
+    def test_energy_is_conserved(self):
+        _, q, w = self.top.run(1.0, 1e-3)
+        energy = self.top.energy(q, w)
+
+        self.assertEqual(energy.shape, (1001,))
+        self.assertLess(np.max(np.abs(energy / energy[0] - 1.0)), 1e-8)
+
+    def test_invalid_arguments(self):
+        with self.assertRaises(ValueError):
+            self.top.set_state([math.nan, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0])
+        with self.assertRaises(ValueError):
+            self.top.run(1e9, 1e-9)
+        for size, density in [(math.inf, 1.0), (1.0, math.nan), (-1.0, 1.0)]:
+            with self.assertRaises(ValueError):
+                Top.cube(size, density)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,503 @@
+use std::{f64::consts::PI, fmt::Display};
+
+use nalgebra::{Matrix3, Rotation3, SymmetricEigen, UnitQuaternion, Vector3};
+
+use crate::{
+    elliptic::{complete_first_kind, incomplete_first_kind, integrate, integrate_periodic, jacobi},
+    initial_conditions::EulerSequence,
+    rigid_body::RigidBody,
+    simulation::TopState,
+};
+
+// Closed-form (up to one periodic quadrature) motion of the top from a given state, used as
+// ground truth for the integrators. Only bodies and states that have one are covered.
+#[derive(Debug, Clone)]
+pub struct AnalyticSolution {
+    start: f64,
+    reference: Reference,
+}
+
+#[derive(Debug, Clone)]
+enum Reference {
+    Steady(SteadyRotation),
+    TorqueFree(TorqueFreeTop),
+    Lagrange(LagrangeTop),
+}
+
+impl AnalyticSolution {
+    pub fn new(body: &RigidBody<f64>, state: &TopState<f64>, gravity: bool) -> Option<Self> {
+        let inertia = body.moment_of_interia();
+        let momentum = inertia * state.w;
+        let center = if gravity {
+            body.center_of_mass()
+        } else {
+            Vector3::zeros()
+        };
+        let torque_free = center.norm() <= 1e-9;
+        let balanced =
+            torque_free || (state.q * center).cross(&Vector3::y()).norm() <= 1e-9 * center.norm();
+
+        let reference = if state.w.cross(&momentum).norm()
+            <= 1e-9 * state.w.norm() * momentum.norm()
+            && balanced
+        {
+            Reference::Steady(SteadyRotation {
+                orientation: state.q,
+                angular_velocity: state.w,
+            })
+        } else if torque_free {
+            Reference::TorqueFree(TorqueFreeTop::new(&inertia, state)?)
+        } else {
+            Reference::Lagrange(LagrangeTop::new(body, state)?)
+        };
+
+        Some(Self {
+            start: state.t,
+            reference,
+        })
+    }
+
+    pub fn orientation(&self, t: f64) -> UnitQuaternion<f64> {
+        let t = t - self.start;
+
+        match &self.reference {
+            Reference::Steady(steady) => steady.orientation(t),
+            Reference::TorqueFree(top) => top.orientation(t),
+            Reference::Lagrange(top) => top.orientation(t),
+        }
+    }
+}
+
+impl Display for AnalyticSolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.reference {
+            Reference::Steady(_) => write!(f, "steady rotation"),
+            Reference::TorqueFree(_) => write!(f, "torque-free top (Jacobi elliptic functions)"),
+            Reference::Lagrange(top) => {
+                let (low, high) = top.nutation_bounds();
+                write!(
+                    f,
+                    "Lagrange top, nutation θ ∈ [{:.2}°, {:.2}°]",
+                    low.to_degrees(),
+                    high.to_degrees()
+                )
+            }
+        }
+    }
+}
+
+// Spinning about a principal axis with no torque, or with the centre of mass straight above
+// or below the pivot.
+#[derive(Debug, Clone)]
+struct SteadyRotation {
+    orientation: UnitQuaternion<f64>,
+    angular_velocity: Vector3<f64>,
+}
+
+impl SteadyRotation {
+    fn orientation(&self, t: f64) -> UnitQuaternion<f64> {
+        self.orientation * UnitQuaternion::from_scaled_axis(self.angular_velocity * t)
+    }
+}
+
+// Euler–Poinsot motion (Landau & Lifshitz, Mechanics §37). The principal axes are relabelled
+// a, b, c so that ω_c never changes sign; then ω = (cn, sn, dn)·amplitudes of λt + τ₀, the
+// angular momentum is fixed in space and only the precession angle about it needs quadrature.
+#[derive(Debug, Clone)]
+struct TorqueFreeTop {
+    frame: UnitQuaternion<f64>,
+    inertial: UnitQuaternion<f64>,
+    moments: Vector3<f64>,
+    amplitudes: Vector3<f64>,
+    rate: f64,
+    phase: f64,
+    parameter: f64,
+    angular_momentum: f64,
+    precession: f64,
+    period: f64,
+    precession_per_period: f64,
+}
+
+impl TorqueFreeTop {
+    fn new(inertia: &Matrix3<f64>, state: &TopState<f64>) -> Option<Self> {
+        let eigen = SymmetricEigen::new(*inertia);
+        let values = eigen.eigenvalues;
+        let mut order = [0, 1, 2];
+        order.sort_by(|&i, &j| values[i].total_cmp(&values[j]));
+        let [low, middle, high] = order;
+
+        let energy = state.w.dot(&(inertia * state.w));
+        let momentum = (inertia * state.w).norm_squared();
+        let ascending = if close(values[middle], values[low]) {
+            true
+        } else if close(values[middle], values[high]) {
+            false
+        } else {
+            momentum >= energy * values[middle]
+        };
+        let [a, b, c] = if ascending {
+            [low, middle, high]
+        } else {
+            [high, middle, low]
+        };
+
+        let mut axes = Matrix3::from_columns(&[
+            eigen.eigenvectors.column(a).into_owned(),
+            eigen.eigenvectors.column(b).into_owned(),
+            eigen.eigenvectors.column(c).into_owned(),
+        ]);
+        if axes.determinant() < 0.0 {
+            axes.set_column(1, &-axes.column(1));
+        }
+        let frame = UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(axes));
+        let moments = Vector3::new(values[a], values[b], values[c]);
+        let (ia, ib, ic) = (moments.x, moments.y, moments.z);
+        let w = frame.inverse() * state.w;
+
+        let transverse = energy * ic - momentum;
+        let axial = momentum - energy * ia;
+        let sign = w.z.signum() * if ascending { 1.0 } else { -1.0 };
+        let amplitudes = Vector3::new(
+            sign * (transverse / (ia * (ic - ia))).max(0.0).sqrt(),
+            (transverse / (ib * (ic - ib))).max(0.0).sqrt(),
+            w.z.signum() * (axial / (ic * (ic - ia))).max(0.0).sqrt(),
+        );
+        let rate = ((ic - ib) * axial / (ia * ib * ic)).max(0.0).sqrt();
+        let parameter = ((ib - ia) * transverse / ((ic - ib) * axial)).clamp(0.0, 1.0 - 1e-12);
+
+        if !(rate > 0.0 && rate.is_finite() && amplitudes.x != 0.0 && amplitudes.y != 0.0) {
+            return None;
+        }
+
+        let phase =
+            incomplete_first_kind((w.y / amplitudes.y).atan2(w.x / amplitudes.x), parameter);
+        let angular_momentum = momentum.sqrt();
+        let inertial = rotation_onto(&Vector3::z(), &(state.q * (inertia * state.w)));
+        let [precession, ..] = EulerSequence::Zxz.angles(&(inertial.inverse() * state.q * frame));
+
+        let mut top = Self {
+            frame,
+            inertial,
+            moments,
+            amplitudes,
+            rate,
+            phase,
+            parameter,
+            angular_momentum,
+            precession,
+            period: 2.0 * complete_first_kind(parameter) / rate,
+            precession_per_period: 0.0,
+        };
+        top.precession_per_period = integrate(
+            |t| top.precession_rate(t),
+            0.0,
+            top.period,
+            top.period / 256.0,
+        );
+
+        Some(top)
+    }
+
+    fn angular_velocity(&self, t: f64) -> Vector3<f64> {
+        let (sn, cn, dn) = jacobi(self.rate * t + self.phase, self.parameter);
+
+        self.amplitudes.component_mul(&Vector3::new(cn, sn, dn))
+    }
+
+    fn precession_rate(&self, t: f64) -> f64 {
+        let w = self.angular_velocity(t);
+        let (ia, ib) = (self.moments.x, self.moments.y);
+
+        self.angular_momentum * (ia * w.x * w.x + ib * w.y * w.y)
+            / (ia * ia * w.x * w.x + ib * ib * w.y * w.y)
+    }
+
+    fn orientation(&self, t: f64) -> UnitQuaternion<f64> {
+        let direction =
+            self.moments.component_mul(&self.angular_velocity(t)) / self.angular_momentum;
+        let nutation = direction.z.clamp(-1.0, 1.0).acos();
+        let spin = direction.x.atan2(direction.y);
+        let precession = self.precession
+            + integrate_periodic(
+                |t| self.precession_rate(t),
+                t,
+                self.period,
+                self.precession_per_period,
+            );
+
+        self.inertial
+            * EulerSequence::Zxz.rotation([precession, nutation, spin])
+            * self.frame.inverse()
+    }
+}
+
+// Heavy symmetric top with the centre of mass on the symmetry axis (Goldstein, Classical
+// Mechanics §5.7). With u = cos θ the effective-potential cubic
+// f(u) = (1 - u²)(α - βu) - (b - au)² has roots u₁ ≤ u₂ ≤ 1 ≤ u₃, the nutation runs between
+// u₁ and u₂ as u = u₁ + (u₂ - u₁) sn²(λt + τ₀), and φ, ψ follow by quadrature. Angles are
+// Y-X-Y, matching Goldstein's Z-X-Z with the vertical along y.
+#[derive(Debug, Clone)]
+struct LagrangeTop {
+    frame: UnitQuaternion<f64>,
+    a: f64,
+    b: f64,
+    spin: f64,
+    roots: [f64; 3],
+    rate: f64,
+    phase: f64,
+    parameter: f64,
+    precession: f64,
+    rotation: f64,
+    period: f64,
+    precession_per_period: f64,
+    rotation_per_period: f64,
+}
+
+impl LagrangeTop {
+    fn new(body: &RigidBody<f64>, state: &TopState<f64>) -> Option<Self> {
+        let inertia = body.moment_of_interia();
+        let center = body.center_of_mass();
+        let eigen = SymmetricEigen::new(inertia);
+        let values = eigen.eigenvalues;
+
+        let mut axis = if close(values[0], values[1]) && close(values[1], values[2]) {
+            center.normalize()
+        } else {
+            let symmetry = (0..3).find(|&i| close(values[(i + 1) % 3], values[(i + 2) % 3]))?;
+            eigen.eigenvectors.column(symmetry).into_owned()
+        };
+        if center.cross(&axis).norm() > 1e-9 * center.norm() {
+            return None;
+        }
+        if center.dot(&axis) < 0.0 {
+            axis = -axis;
+        }
+
+        let arm = center.dot(&axis);
+        let axial_moment = axis.dot(&(inertia * axis));
+        let transverse_moment = (inertia.trace() - axial_moment) / 2.0;
+        let frame = rotation_onto(&Vector3::y(), &axis);
+
+        let spin = state.w.dot(&axis);
+        let energy =
+            state.w.dot(&(inertia * state.w)) / 2.0 + body.mass() * 9.81 * (state.q * center).y;
+        let alpha = (2.0 * energy - axial_moment * spin * spin) / transverse_moment;
+        let beta = 2.0 * body.mass() * 9.81 * arm / transverse_moment;
+        let a = axial_moment * spin / transverse_moment;
+        let b = (state.q * (inertia * state.w)).y / transverse_moment;
+
+        let [precession, nutation, rotation] = EulerSequence::Yxy.angles(&(state.q * frame));
+        let u = nutation.cos();
+        // The Euler angles are singular with the symmetry axis vertical.
+        if 1.0 - u.abs() < 1e-9 {
+            return None;
+        }
+
+        let roots = cubic_roots([beta, -(alpha + a * a), 2.0 * a * b - beta, alpha - b * b])?;
+        let roots = [roots[0].min(u), roots[1].max(u), roots[2]];
+        let width = roots[1] - roots[0];
+        let rate = (beta * (roots[2] - roots[0]) / 4.0).sqrt();
+        let parameter = (width / (roots[2] - roots[0])).clamp(0.0, 1.0 - 1e-12);
+
+        // Near a turning point sn or cn is the square root of a difference of nearly equal
+        // numbers, so the small one is taken from u' = 2(u₂ - u₁)λ sn cn dn instead.
+        let u_rate = (state.q * state.w).cross(&(state.q * axis)).y;
+        let (sn, cn) = if width > 0.0 {
+            let sn_squared = ((u - roots[0]) / width).clamp(0.0, 1.0);
+            let scale = 2.0 * width * rate * (1.0 - parameter * sn_squared).sqrt();
+            if sn_squared > 0.5 {
+                let sn = sn_squared.sqrt();
+                (sn, u_rate / (scale * sn))
+            } else {
+                let cn = (1.0 - sn_squared).sqrt();
+                (u_rate / (scale * cn), cn)
+            }
+        } else {
+            (0.0, 1.0)
+        };
+        let phase = incomplete_first_kind(sn.atan2(cn), parameter);
+
+        let mut top = Self {
+            frame,
+            a,
+            b,
+            spin,
+            roots,
+            rate,
+            phase,
+            parameter,
+            precession,
+            rotation,
+            period: 2.0 * complete_first_kind(parameter) / rate,
+            precession_per_period: 0.0,
+            rotation_per_period: 0.0,
+        };
+        top.precession_per_period = integrate(
+            |t| top.precession_rate(t),
+            0.0,
+            top.period,
+            top.period / 256.0,
+        );
+        top.rotation_per_period = integrate(
+            |t| top.cos_nutation(t) * top.precession_rate(t),
+            0.0,
+            top.period,
+            top.period / 256.0,
+        );
+
+        Some(top)
+    }
+
+    fn nutation_bounds(&self) -> (f64, f64) {
+        (self.roots[1].acos(), self.roots[0].acos())
+    }
+
+    fn cos_nutation(&self, t: f64) -> f64 {
+        let (sn, ..) = jacobi(self.rate * t + self.phase, self.parameter);
+
+        self.roots[0] + (self.roots[1] - self.roots[0]) * sn * sn
+    }
+
+    fn precession_rate(&self, t: f64) -> f64 {
+        let u = self.cos_nutation(t);
+        let sin_squared = 1.0 - u * u;
+
+        // Passing through the vertical is only possible with b = ±a, where the limit is ±a/2.
+        if sin_squared < 1e-12 {
+            return self.a / 2.0 * u.signum();
+        }
+
+        (self.b - self.a * u) / sin_squared
+    }
+
+    fn orientation(&self, t: f64) -> UnitQuaternion<f64> {
+        let nutation = self.cos_nutation(t).clamp(-1.0, 1.0).acos();
+        let precession = self.precession
+            + integrate_periodic(
+                |t| self.precession_rate(t),
+                t,
+                self.period,
+                self.precession_per_period,
+            );
+        let rotation = self.rotation + self.spin * t
+            - integrate_periodic(
+                |t| self.cos_nutation(t) * self.precession_rate(t),
+                t,
+                self.period,
+                self.rotation_per_period,
+            );
+
+        EulerSequence::Yxy.rotation([precession, nutation, rotation]) * self.frame.inverse()
+    }
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= 1e-9 * a.abs().max(b.abs())
+}
+
+fn rotation_onto(from: &Vector3<f64>, to: &Vector3<f64>) -> UnitQuaternion<f64> {
+    UnitQuaternion::rotation_between(from, to)
+        .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector3::x_axis(), PI))
+}
+
+// Ascending roots of the cubic with coefficients [x³, x², x, 1], when all three are real.
+fn cubic_roots(coefficients: [f64; 4]) -> Option<[f64; 3]> {
+    let [c3, c2, c1, c0] = coefficients;
+    let (p2, p1, p0) = (c2 / c3, c1 / c3, c0 / c3);
+    let p = p1 - p2 * p2 / 3.0;
+    let q = 2.0 * p2 * p2 * p2 / 27.0 - p2 * p1 / 3.0 + p0;
+
+    if p >= 0.0 {
+        return None;
+    }
+
+    let radius = 2.0 * (-p / 3.0).sqrt();
+    let angle = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt())
+        .clamp(-1.0, 1.0)
+        .acos()
+        / 3.0;
+    let mut roots = [0.0, 1.0, 2.0].map(|k| {
+        let mut x = radius * (angle - 2.0 * PI * k / 3.0).cos() - p2 / 3.0;
+        for _ in 0..2 {
+            let value = ((x + p2) * x + p1) * x + p0;
+            let slope = (3.0 * x + 2.0 * p2) * x + p1;
+            if slope != 0.0 {
+                x -= value / slope;
+            }
+        }
+        x
+    });
+    roots.sort_by(f64::total_cmp);
+
+    Some(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{UnitQuaternion, Vector3};
+
+    use super::AnalyticSolution;
+    use crate::{
+        body_definition::PrincipalInertia,
+        integrator::IntegratorKind,
+        rigid_body::RigidBody,
+        simulation::{Simulator, TopState},
+    };
+
+    // Largest angle between the reference and an RK4 run at h = 1e-4 over 5 s, sampled every
+    // half second.
+    fn largest_error(inertia: &PrincipalInertia, state: TopState<f64>, kind: &str) -> f64 {
+        let body = RigidBody::from_principal_moments(inertia);
+        let reference = AnalyticSolution::new(&body, &state, true).unwrap();
+        assert!(reference.to_string().starts_with(kind), "{}", reference);
+        let mut simulator =
+            Simulator::new(&body, IntegratorKind::RungeKutta4.create(), 1e-4, state);
+
+        let mut error = 0.0f64;
+        while simulator.steps() < 50_000 {
+            simulator.advance_step();
+            if simulator.steps() % 5_000 == 0 {
+                let state = simulator.state();
+                error = error.max(state.q.angle_to(&reference.orientation(state.t)));
+            }
+        }
+
+        error
+    }
+
+    #[test]
+    fn torque_free_top_matches_rk4() {
+        let inertia = PrincipalInertia {
+            mass: 1.0,
+            moments: [1.0, 2.0, 3.0],
+            axes: [0.0; 3],
+            center_of_mass: [0.0; 3],
+        };
+        let state = TopState::new(
+            UnitQuaternion::from_euler_angles(0.2, 0.1, 0.0),
+            Vector3::new(0.5, 3.0, 0.4),
+            0.0,
+        );
+
+        let error = largest_error(&inertia, state, "torque-free top");
+        assert!(error < 1e-9, "largest error {} rad", error);
+    }
+
+    #[test]
+    fn lagrange_top_matches_rk4() {
+        let inertia = PrincipalInertia {
+            mass: 1.0,
+            moments: [2.0, 1.0, 2.0],
+            axes: [0.0; 3],
+            center_of_mass: [0.0, 0.5, 0.0],
+        };
+        let state = TopState::new(
+            UnitQuaternion::from_euler_angles(0.3, 0.0, 0.0),
+            Vector3::new(0.2, 10.0, 0.0),
+            0.0,
+        );
+
+        let error = largest_error(&inertia, state, "Lagrange top");
+        assert!(error < 1e-9, "largest error {} rad", error);
+    }
+}
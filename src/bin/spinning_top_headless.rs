@@ -0,0 +1,208 @@
+use std::{fs::File, io::BufWriter, path::Path, process::ExitCode, str::FromStr};
+
+use serde_json::json;
+use spinning_top::{
+    export, run_headless, BodyDefinition, Config, Divergence, ExportFormat, ExportSettings,
+    HeadlessSettings, IntegratorKind, Precision, Shape,
+};
+
+const USAGE: &str = "usage: spinning_top_headless [options]
+
+Runs a simulation without a display and writes its history and a summary.
+
+  --config <file>            start from a saved .toml or .json configuration
+  --end-time <s>             simulated time to stop at, required unless the config sets one
+  --size <m>                 cube size
+  --density <kg/m³>          shape density
+  --deviation <rad>          initial tilt about x
+  --spin <rad/s>             initial spin about the body's y axis
+  --step <s>                 integration step
+  --integrator <name>        euler, rk4, dormand-prince or crouch-grossman
+  --precision <p>            f32 or f64
+  --gravity <bool>           true or false
+  --decimation <n>           keep every n-th step in the history
+  --energy-tolerance <rel>   largest relative energy drift before the run counts as diverged
+  --history <file>           .csv, .jsonl or .npy, default history.csv
+  --summary <file>           JSON summary, default summary.json
+
+exit codes: 0 finished, 1 invalid arguments or I/O error,
+            2 the state became non-finite, 3 the energy drift exceeded the tolerance";
+
+const EXIT_FAILURE: u8 = 1;
+const EXIT_NON_FINITE: u8 = 2;
+const EXIT_ENERGY_DRIFT: u8 = 3;
+
+struct Options {
+    config: Config,
+    decimation: u64,
+    energy_tolerance: Option<f64>,
+    history: String,
+    summary: String,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_arguments(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    };
+
+    let Some(end_time) = options.config.end_time else {
+        eprintln!(
+            "no end time, set --end-time or end_time in the config\n\n{}",
+            USAGE
+        );
+        return ExitCode::from(EXIT_FAILURE);
+    };
+
+    let parameters = options.config.simulation_parameters();
+    let (history, summary) = run_headless(
+        &parameters,
+        &HeadlessSettings::new(end_time, options.decimation, options.energy_tolerance),
+    );
+
+    let format = Path::new(&options.history)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(ExportFormat::from_extension)
+        .unwrap_or(ExportFormat::Csv);
+    let settings = ExportSettings {
+        format,
+        ..ExportSettings::default()
+    };
+    if let Err(error) = export(
+        &options.history,
+        &settings,
+        &parameters.build_body(),
+        &history,
+    ) {
+        eprintln!("{}: {}", options.history, error);
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    let written = File::create(&options.summary)
+        .map_err(serde_json::Error::io)
+        .and_then(|file| {
+            serde_json::to_writer_pretty(
+                BufWriter::new(file),
+                &json!({ "parameters": parameters, "summary": summary }),
+            )
+        });
+    if let Err(error) = written {
+        eprintln!("{}: {}", options.summary, error);
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    println!(
+        "t = {:.4} s after {} steps, max energy drift {:.3e}, max tilt {:.4} rad",
+        summary.simulated_time, summary.steps, summary.max_energy_drift, summary.max_tilt
+    );
+
+    match summary.divergence {
+        None => ExitCode::SUCCESS,
+        Some(divergence) => {
+            eprintln!("diverged at t = {}: {}", summary.simulated_time, divergence);
+            ExitCode::from(match divergence {
+                Divergence::NonFinite => EXIT_NON_FINITE,
+                Divergence::EnergyDrift => EXIT_ENERGY_DRIFT,
+            })
+        }
+    }
+}
+
+// The config is applied first, so the other flags override it wherever they appear.
+fn parse_arguments(arguments: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut flags = Vec::new();
+    let mut arguments = arguments;
+    while let Some(flag) = arguments.next() {
+        if flag == "--help" || flag == "-h" {
+            println!("{}", USAGE);
+            std::process::exit(0);
+        }
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("{} needs a value", flag))?;
+        flags.push((flag, value));
+    }
+
+    let mut options = Options {
+        config: Config::default(),
+        decimation: 1,
+        energy_tolerance: None,
+        history: "history.csv".to_string(),
+        summary: "summary.json".to_string(),
+    };
+
+    if let Some((_, path)) = flags.iter().rev().find(|(flag, _)| flag == "--config") {
+        options.config = Config::load(path).map_err(|error| format!("{}: {}", path, error))?;
+    }
+
+    for (flag, value) in &flags {
+        match flag.as_str() {
+            "--config" => {}
+            "--end-time" => options.config.end_time = Some(value_of(flag, value)?),
+            "--size" => match &mut options.config.body {
+                BodyDefinition::Shape {
+                    shape: Shape::Cube { size },
+                    ..
+                } => *size = value_of(flag, value)?,
+                _ => return Err("--size needs a cube body".to_string()),
+            },
+            "--density" => match &mut options.config.body {
+                BodyDefinition::Shape { density, .. } => *density = value_of(flag, value)?,
+                BodyDefinition::Inertia(_) => {
+                    return Err("--density needs a shape body".to_string())
+                }
+            },
+            "--deviation" => options.config.set_deviation(value_of(flag, value)?),
+            "--spin" => options.config.set_spin(value_of(flag, value)?),
+            "--step" => options.config.integration_step = value_of(flag, value)?,
+            "--integrator" => {
+                options.config.integrator = match value.as_str() {
+                    "euler" => IntegratorKind::Euler,
+                    "rk4" => IntegratorKind::RungeKutta4,
+                    "dormand-prince" => IntegratorKind::DormandPrince,
+                    "crouch-grossman" => IntegratorKind::CrouchGrossman,
+                    _ => return Err(format!("unknown integrator {:?}", value)),
+                }
+            }
+            "--precision" => {
+                options.config.precision = Precision::ALL
+                    .into_iter()
+                    .find(|precision| precision.to_string() == *value)
+                    .ok_or_else(|| format!("unknown precision {:?}", value))?;
+            }
+            "--gravity" => options.config.gravity = value_of(flag, value)?,
+            "--decimation" => options.decimation = value_of(flag, value)?,
+            "--energy-tolerance" => options.energy_tolerance = Some(value_of(flag, value)?),
+            "--history" => options.history = value.clone(),
+            "--summary" => options.summary = value.clone(),
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+
+    options
+        .config
+        .validate()
+        .map_err(|error| error.to_string())?;
+
+    if options.decimation == 0 {
+        return Err("the decimation must be at least 1".to_string());
+    }
+    if options
+        .energy_tolerance
+        .is_some_and(|tolerance| !(tolerance.is_finite() && tolerance > 0.0))
+    {
+        return Err("the energy tolerance must be positive".to_string());
+    }
+
+    Ok(options)
+}
+
+fn value_of<T: FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value {:?} for {}", value, flag))
+}
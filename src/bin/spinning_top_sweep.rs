@@ -0,0 +1,66 @@
+use std::{io::Write, process::ExitCode, time::Instant};
+
+use spinning_top::Sweep;
+
+const USAGE: &str = "usage: spinning_top_sweep <sweep.toml|sweep.json> [results.csv]
+
+Runs every combination of the swept values in parallel and writes one row of metrics per
+run, by default to results.csv. The sweep file lists values for any of cube_size, density,
+deviation, angular_velocity, integration_step, gravity and integrator, plus end_time,
+energy_tolerance and a [base] configuration for everything else, e.g.
+
+  end_time = 20.0
+  deviation = [0.1, 0.2, 0.3]
+  angular_velocity = [10.0, 20.0, 40.0]
+  integrator = [\"RungeKutta4\", \"DormandPrince\"]
+
+  [base]
+  precision = \"Double\"
+
+Set RAYON_NUM_THREADS to limit the number of cores used.";
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = std::env::args().skip(1).collect();
+    let (sweep_path, results_path) = match arguments.as_slice() {
+        [sweep] if !sweep.starts_with('-') => (sweep.as_str(), "results.csv"),
+        [sweep, results] if !sweep.starts_with('-') => (sweep.as_str(), results.as_str()),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let sweep = match Sweep::load(sweep_path) {
+        Ok(sweep) => sweep,
+        Err(error) => {
+            eprintln!("{}: {}", sweep_path, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let total = sweep.points().len();
+    let start = Instant::now();
+    let results = sweep.run(|finished| {
+        eprint!("\r{}/{} runs", finished, total);
+        let _ = std::io::stderr().flush();
+    });
+    eprintln!(" in {:.1} s", start.elapsed().as_secs_f64());
+
+    if let Err(error) = sweep.write_csv(results_path, &results) {
+        eprintln!("{}: {}", results_path, error);
+        return ExitCode::FAILURE;
+    }
+
+    let diverged = results
+        .iter()
+        .filter(|result| result.summary.divergence.is_some())
+        .count();
+    println!(
+        "wrote {} runs to {}, {} diverged",
+        results.len(),
+        results_path,
+        diverged
+    );
+
+    ExitCode::SUCCESS
+}
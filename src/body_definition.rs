@@ -0,0 +1,151 @@
+use std::fmt::Display;
+
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    pivot::Pivot,
+    real::{real, Real},
+    rigid_body::RigidBody,
+    shape::Shape,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PrincipalInertia {
+    pub mass: f64,
+    pub moments: [f64; 3],
+    pub axes: [f64; 3],
+    pub center_of_mass: [f64; 3],
+}
+
+impl PrincipalInertia {
+    // Body y is the symmetry axis wherever the preset has one.
+    pub const PRESETS: [(&'static str, PrincipalInertia); 3] = [
+        (
+            "Euler",
+            PrincipalInertia {
+                mass: 1.0,
+                moments: [0.5, 1.0, 0.75],
+                axes: [0.0; 3],
+                center_of_mass: [0.0; 3],
+            },
+        ),
+        (
+            "Lagrange",
+            PrincipalInertia {
+                mass: 1.0,
+                moments: [1.0, 0.5, 1.0],
+                axes: [0.0; 3],
+                center_of_mass: [0.0, 0.5, 0.0],
+            },
+        ),
+        (
+            "Kovalevskaya",
+            PrincipalInertia {
+                mass: 1.0,
+                moments: [2.0, 1.0, 2.0],
+                axes: [0.0; 3],
+                center_of_mass: [0.5, 0.0, 0.0],
+            },
+        ),
+    ];
+
+    pub fn preset_name(&self) -> Option<&'static str> {
+        Self::PRESETS
+            .iter()
+            .find(|(_, preset)| preset == self)
+            .map(|(name, _)| *name)
+    }
+
+    pub fn is_physical(&self) -> bool {
+        let [x, y, z] = self.moments;
+        let finite = [self.mass, x, y, z]
+            .iter()
+            .chain(&self.axes)
+            .chain(&self.center_of_mass)
+            .all(|value| value.is_finite());
+
+        finite
+            && self.mass > 0.0
+            && x > 0.0
+            && y > 0.0
+            && z > 0.0
+            && x + y >= z
+            && y + z >= x
+            && x + z >= y
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BodyDefinition {
+    Shape {
+        shape: Shape,
+        density: f64,
+        pivot: Pivot,
+        unbalance: [f64; 3],
+    },
+    Inertia(PrincipalInertia),
+}
+
+impl BodyDefinition {
+    // Whether a rigid body can be built from the definition as written; anything else has an
+    // inertia that cannot be inverted or is not finite, or a pivot the shape does not have.
+    pub fn is_physical(&self) -> bool {
+        match self {
+            BodyDefinition::Shape {
+                shape,
+                density,
+                pivot,
+                unbalance,
+            } => {
+                let pivot_defined = pivot
+                    .point::<f64>(shape)
+                    .is_some_and(|point| point.iter().all(|x| x.is_finite()));
+
+                shape.is_physical()
+                    && density.is_finite()
+                    && *density > 0.0
+                    && pivot_defined
+                    && unbalance.iter().all(|x| x.is_finite())
+            }
+            BodyDefinition::Inertia(inertia) => inertia.is_physical(),
+        }
+    }
+
+    pub fn build<T: Real>(&self) -> RigidBody<T> {
+        match self {
+            BodyDefinition::Shape {
+                shape,
+                density,
+                pivot,
+                unbalance,
+            } => RigidBody::from_shape(
+                shape.clone(),
+                real(*density),
+                *pivot,
+                Vector3::from(unbalance.map(real::<T>)),
+            ),
+            BodyDefinition::Inertia(inertia) => RigidBody::from_principal_moments(inertia),
+        }
+    }
+}
+
+impl Default for BodyDefinition {
+    fn default() -> Self {
+        BodyDefinition::Shape {
+            shape: Shape::Cube { size: 1.0 },
+            density: 1.0,
+            pivot: Pivot::Natural,
+            unbalance: [0.0; 3],
+        }
+    }
+}
+
+impl Display for BodyDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyDefinition::Shape { .. } => write!(f, "shape"),
+            BodyDefinition::Inertia(_) => write!(f, "inertia"),
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use std::{mem::discriminant, ops::RangeInclusive};
+
+use egui::{Color32, DragValue, Ui, Widget};
+
+use crate::{
+    body_definition::{BodyDefinition, PrincipalInertia},
+    pivot::Pivot,
+    shape::Shape,
+    shape_editor::ShapeEditor,
+};
+
+#[derive(Default)]
+pub struct BodyEditor {
+    shape_editor: ShapeEditor,
+    stashed: Option<BodyDefinition>,
+}
+
+impl BodyEditor {
+    pub fn ui(&mut self, ui: &mut Ui, body: &mut BodyDefinition) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            for kind in [
+                BodyDefinition::default(),
+                BodyDefinition::Inertia(PrincipalInertia::PRESETS[1].1),
+            ] {
+                let selected = discriminant(body) == discriminant(&kind);
+
+                if ui.radio(selected, kind.to_string()).clicked() && !selected {
+                    let replacement = self
+                        .stashed
+                        .take()
+                        .filter(|stashed| discriminant(stashed) == discriminant(&kind))
+                        .unwrap_or(kind);
+                    self.stashed = Some(std::mem::replace(body, replacement));
+                    changed = true;
+                }
+            }
+
+            ui.label("body");
+        });
+
+        match body {
+            BodyDefinition::Shape {
+                shape,
+                density,
+                pivot,
+                unbalance,
+            } => {
+                changed |= self.shape_editor.ui(ui, shape);
+
+                ui.horizontal(|ui| {
+                    changed |= DragValue::new(density)
+                        .clamp_range(0.01..=10.0)
+                        .speed(0.01)
+                        .ui(ui)
+                        .changed();
+
+                    ui.label("density");
+                });
+
+                changed |= Self::pivot_ui(ui, shape, pivot);
+                changed |= triple(ui, unbalance, -1.0..=1.0, "centre of mass unbalance");
+            }
+            BodyDefinition::Inertia(inertia) => changed |= Self::inertia_ui(ui, inertia),
+        }
+
+        changed
+    }
+
+    fn pivot_ui(ui: &mut Ui, shape: &Shape, pivot: &mut Pivot) -> bool {
+        let mut changed = false;
+
+        if pivot.point::<f64>(shape).is_none() {
+            *pivot = Pivot::Natural;
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("pivot")
+                .selected_text(pivot.to_string())
+                .show_ui(ui, |ui| {
+                    for kind in Pivot::KINDS {
+                        if kind.point::<f64>(shape).is_some()
+                            && ui
+                                .selectable_label(pivot.same_kind(&kind), kind.to_string())
+                                .clicked()
+                            && !pivot.same_kind(&kind)
+                        {
+                            *pivot = kind;
+                            changed = true;
+                        }
+                    }
+                });
+
+            ui.label("pivot");
+        });
+
+        let count = pivot.count();
+
+        match pivot {
+            Pivot::Vertex(index) | Pivot::EdgeMidpoint(index) | Pivot::FaceCenter(index) => {
+                ui.horizontal(|ui| {
+                    changed |= DragValue::new(index)
+                        .clamp_range(0..=count - 1)
+                        .ui(ui)
+                        .changed();
+
+                    ui.label("index");
+                });
+            }
+            Pivot::Custom(point) => {
+                changed |= triple(ui, point, -10.0..=10.0, "pivot point");
+            }
+            Pivot::Natural => {}
+        }
+
+        changed
+    }
+
+    fn inertia_ui(ui: &mut Ui, inertia: &mut PrincipalInertia) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("inertia preset")
+                .selected_text(inertia.preset_name().unwrap_or("custom"))
+                .show_ui(ui, |ui| {
+                    for (name, preset) in PrincipalInertia::PRESETS {
+                        if ui.selectable_label(*inertia == preset, name).clicked() {
+                            *inertia = preset;
+                            changed = true;
+                        }
+                    }
+                });
+
+            ui.label("preset");
+        });
+
+        ui.horizontal(|ui| {
+            changed |= DragValue::new(&mut inertia.mass)
+                .clamp_range(0.01..=10.0)
+                .speed(0.01)
+                .ui(ui)
+                .changed();
+
+            ui.label("mass");
+        });
+
+        changed |= triple(ui, &mut inertia.moments, 0.01..=10.0, "principal moments");
+        changed |= triple(
+            ui,
+            &mut inertia.axes,
+            (-std::f64::consts::PI)..=std::f64::consts::PI,
+            "principal axes (roll, pitch, yaw)",
+        );
+        changed |= triple(
+            ui,
+            &mut inertia.center_of_mass,
+            -5.0..=5.0,
+            "centre of mass",
+        );
+
+        if !inertia.is_physical() {
+            ui.colored_label(
+                Color32::YELLOW,
+                "moments violate the triangle inequality, no real body has them",
+            );
+        }
+
+        changed
+    }
+}
+
+pub fn triple(ui: &mut Ui, values: &mut [f64; 3], range: RangeInclusive<f64>, label: &str) -> bool {
+    let mut changed = false;
+
+    ui.horizontal(|ui| {
+        for value in values {
+            changed |= DragValue::new(value)
+                .clamp_range(range.clone())
+                .speed(0.01)
+                .ui(ui)
+                .changed();
+        }
+
+        ui.label(label);
+    });
+
+    changed
+}
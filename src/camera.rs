@@ -0,0 +1,44 @@
+use nalgebra::{Matrix4, Point3, Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+
+// Orbits the origin at `distance`, turned by `pitch` and `yaw` in radians.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Camera {
+    pub pitch: f32,
+    pub yaw: f32,
+    pub distance: f32,
+}
+
+impl Camera {
+    pub fn rotate(&mut self, dx: f32, dy: f32) {
+        self.pitch += dy * 0.01;
+        // Past the pole the view is upside down, so horizontal dragging turns the other way.
+        self.yaw += dx * 0.01 * if self.pitch.cos() < 0.0 { -1.0 } else { 1.0 };
+    }
+
+    pub fn zoom(&mut self, delta: f32) {
+        self.distance += delta;
+    }
+
+    pub fn view(&self) -> Matrix4<f32> {
+        let rotation = Matrix4::from_euler_angles(self.pitch, self.yaw, 0.0);
+        let direction = (rotation * Vector4::new(0.0, 0.0, 1.0, 0.0)).xyz();
+        let up: Vector3<f32> = (rotation * Vector4::new(0.0, 1.0, 0.0, 0.0)).xyz();
+
+        Matrix4::look_at_rh(
+            &Point3::from(-self.distance * direction),
+            &Point3::origin(),
+            &up,
+        )
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            pitch: 0.0,
+            yaw: 0.0,
+            distance: 5.0,
+        }
+    }
+}
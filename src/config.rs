@@ -0,0 +1,224 @@
+use std::{fmt::Display, fs, io, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    body_definition::BodyDefinition,
+    camera::Camera,
+    initial_conditions::{InitialConditions, VelocityFrame},
+    integrator::IntegratorKind,
+    real::Precision,
+    simulation_clock::ClockMode,
+    simulation_parameters::{SimulationParameters, StepControllerSettings},
+};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    UnsupportedFormat(String),
+    Toml(String),
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(error) => write!(f, "{}", error),
+            ConfigError::UnsupportedFormat(extension) => {
+                write!(f, "unsupported configuration format: {:?}", extension)
+            }
+            ConfigError::Toml(error) => write!(f, "invalid TOML: {}", error),
+            ConfigError::Json(error) => write!(f, "invalid JSON: {}", error),
+            ConfigError::Invalid(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(error: io::Error) -> Self {
+        ConfigError::Io(error)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(error: toml::de::Error) -> Self {
+        ConfigError::Toml(error.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for ConfigError {
+    fn from(error: toml::ser::Error) -> Self {
+        ConfigError::Toml(error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(error: serde_json::Error) -> Self {
+        ConfigError::Json(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrawSettings {
+    pub body: bool,
+    pub diagonal: bool,
+    pub trajectory: bool,
+    pub gravity_vector: bool,
+    pub angular_velocity: bool,
+}
+
+impl Default for DrawSettings {
+    fn default() -> Self {
+        Self {
+            body: true,
+            diagonal: true,
+            trajectory: true,
+            gravity_vector: true,
+            angular_velocity: true,
+        }
+    }
+}
+
+// Everything set up through the panel, so an experiment can be saved and set up again.
+// Missing fields take their defaults, so a file only needs the settings it changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub body: BodyDefinition,
+    pub initial: InitialConditions,
+    pub integration_step: f64,
+    pub integrator: IntegratorKind,
+    pub precision: Precision,
+    pub adaptive_step: bool,
+    pub step_controller: StepControllerSettings,
+    pub clock_mode: ClockMode,
+    pub end_time: Option<f64>,
+    pub gravity: bool,
+    pub compare_to_analytic: bool,
+    pub history_decimation: u64,
+    pub trajectory_size: usize,
+    pub draw: DrawSettings,
+    pub camera: Camera,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            body: BodyDefinition::default(),
+            initial: InitialConditions::default(),
+            integration_step: 0.001,
+            integrator: IntegratorKind::RungeKutta4,
+            precision: Precision::Single,
+            adaptive_step: false,
+            step_controller: StepControllerSettings::new(1e-6, 1e-5, 1e-6, 0.01),
+            clock_mode: ClockMode::RealTime,
+            end_time: None,
+            gravity: true,
+            compare_to_analytic: false,
+            history_decimation: 1,
+            trajectory_size: 500000,
+            draw: DrawSettings::default(),
+            camera: Camera::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let config: Self = read(path)?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    // The step controller is only checked when it is switched on.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.simulation_parameters().validate()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ConfigError> {
+        let text = match Format::of(path.as_ref())? {
+            Format::Toml => toml::to_string_pretty(self)?,
+            Format::Json => serde_json::to_string_pretty(self)?,
+        };
+
+        Ok(fs::write(path, text)?)
+    }
+
+    // Tilts the top about x, as `InitialConditions::tilted` does.
+    pub fn set_deviation(&mut self, deviation: f64) {
+        self.initial.orientation = InitialConditions::tilted(deviation, 0.0).orientation;
+    }
+
+    // Spins the top about its body y axis.
+    pub fn set_spin(&mut self, spin: f64) {
+        self.initial.angular_velocity = [0.0, spin, 0.0];
+        self.initial.frame = VelocityFrame::Body;
+    }
+
+    pub fn simulation_parameters(&self) -> SimulationParameters {
+        SimulationParameters {
+            body: self.body.clone(),
+            initial: self.initial,
+            integration_step: self.integration_step,
+            integrator: self.integrator,
+            step_controller: self.adaptive_step.then_some(self.step_controller),
+            gravity: self.gravity,
+            precision: self.precision,
+            analytic_reference: self.compare_to_analytic,
+            end_time: self.end_time,
+            start_state: None,
+        }
+    }
+
+    // Takes over the settings of a run, e.g. a loaded recording, and leaves the view alone.
+    pub fn apply_run(&mut self, parameters: &SimulationParameters) {
+        self.body = parameters.body.clone();
+        self.initial = parameters.initial;
+        self.integration_step = parameters.integration_step;
+        self.integrator = parameters.integrator;
+        self.adaptive_step = parameters.step_controller.is_some();
+        if let Some(settings) = parameters.step_controller {
+            self.step_controller = settings;
+        }
+        self.gravity = parameters.gravity;
+        self.precision = parameters.precision;
+        self.compare_to_analytic = parameters.analytic_reference;
+        self.end_time = parameters.end_time;
+    }
+}
+
+// The format follows the extension, `.toml` or `.json`.
+pub fn read<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, ConfigError> {
+    let format = Format::of(path.as_ref())?;
+    let text = fs::read_to_string(path)?;
+
+    match format {
+        Format::Toml => Ok(toml::from_str(&text)?),
+        Format::Json => Ok(serde_json::from_str(&text)?),
+    }
+}
+
+enum Format {
+    Toml,
+    Json,
+}
+
+impl Format {
+    fn of(path: &Path) -> Result<Self, ConfigError> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => Ok(Format::Toml),
+            "json" => Ok(Format::Json),
+            _ => Err(ConfigError::UnsupportedFormat(extension.to_string())),
+        }
+    }
+}
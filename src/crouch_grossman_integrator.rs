@@ -0,0 +1,53 @@
+use nalgebra::{UnitQuaternion, Vector3};
+
+use crate::{
+    integrator::{Integrator, StepResult},
+    real::{real, Real},
+    simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+// Third order Crouch-Grossman scheme. The orientation is only ever updated by
+// composing exact rotations, so it never leaves SO(3) and needs no renormalisation.
+const A: [[f64; 2]; 3] = [[0.0, 0.0], [3.0 / 4.0, 0.0], [119.0 / 216.0, 17.0 / 108.0]];
+const B: [f64; 3] = [13.0 / 51.0, -2.0 / 3.0, 24.0 / 17.0];
+
+pub struct CrouchGrossmanIntegrator;
+
+impl CrouchGrossmanIntegrator {
+    fn compose<T: Real>(
+        q: &UnitQuaternion<T>,
+        coefficients: &[f64],
+        w: &[Vector3<T>],
+        h: T,
+    ) -> UnitQuaternion<T> {
+        coefficients.iter().zip(w).fold(*q, |q, (c, w)| {
+            q * UnitQuaternion::from_scaled_axis(w * (h * real(*c)))
+        })
+    }
+}
+
+impl<T: Real> Integrator<T> for CrouchGrossmanIntegrator {
+    fn step(&mut self, dynamics: &TopDynamics<T>, state: &TopState<T>, h: T) -> StepResult<T> {
+        let mut stage_w = [Vector3::zeros(); 3];
+        let mut stage_dw = [Vector3::zeros(); 3];
+
+        for i in 0..3 {
+            let q = Self::compose(&state.q, &A[i][..i], &stage_w[..i], h);
+            stage_w[i] = (0..i).fold(state.w, |sum, j| sum + stage_dw[j] * (h * real(A[i][j])));
+            stage_dw[i] = dynamics.angular_acceleration(&q, &stage_w[i]);
+        }
+
+        let new_q = Self::compose(&state.q, &B, &stage_w, h);
+
+        StepResult::new(
+            TopState::new(
+                new_q,
+                (0..3).fold(state.w, |sum, i| sum + stage_dw[i] * (h * real(B[i]))),
+                state.t + h,
+            ),
+            new_q.quaternion().norm(),
+            None,
+        )
+    }
+}
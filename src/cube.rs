@@ -2,68 +2,77 @@ use derive_builder::Builder;
 use derive_getters::Getters;
 use derive_new::new;
 use derive_setters::Setters;
-use nalgebra::{Matrix3, Matrix4, UnitQuaternion, Vector3};
+use nalgebra::{Matrix3, UnitQuaternion, Vector3};
+
+use crate::real::{real, Real};
 
 #[derive(Debug, Clone, Getters, Setters, new, Builder)]
 #[setters(generate = false, prefix = "set_", borrow_self)]
-pub struct Cube {
+pub struct Cube<T: Real> {
     #[getter(copy)]
     #[setters(generate)]
-    size: f32,
+    size: T,
     #[getter(copy)]
     #[setters(generate)]
-    density: f32,
+    density: T,
     #[getter(copy)]
     #[setters(generate)]
-    rotation: UnitQuaternion<f32>,
+    rotation: UnitQuaternion<T>,
     #[getter(copy)]
-    base_rotation: UnitQuaternion<f32>,
+    base_rotation: UnitQuaternion<T>,
 }
 
-impl Cube {
-    pub fn get_model_matrix(&self) -> Matrix4<f32> {
-        self.rotation.to_rotation_matrix().to_homogeneous()
-            * self.base_rotation.to_rotation_matrix().to_homogeneous()
-            * Matrix4::new_scaling(self.size)
+impl<T: Real> Cube<T> {
+    pub fn standing_on_vertex(size: T, density: T) -> Self {
+        Self::new(
+            size,
+            density,
+            UnitQuaternion::identity(),
+            UnitQuaternion::from_euler_angles(
+                -(real::<T>(2.0) / real(3.0)).sqrt().acos(),
+                T::zero(),
+                T::zero(),
+            ) * UnitQuaternion::from_euler_angles(T::zero(), T::zero(), T::frac_pi_4()),
+        )
     }
 
-    pub fn get_diagonal_model_matrix(&self) -> Matrix4<f32> {
-        self.rotation.to_rotation_matrix().to_homogeneous() * Matrix4::new_scaling(self.size)
+    pub fn get_top(&self) -> Vector3<T> {
+        Vector3::new(T::zero(), self.size * real::<T>(3.0).sqrt(), T::zero())
     }
 
-    pub fn get_gravity_vector_model_matrix(&self) -> Matrix4<f32> {
-        let center = Vector3::new(0f32, self.size * 3f32.sqrt() / 2f32, 0f32);
-        let rotated_center = self.rotation.to_rotation_matrix() * center;
-        Matrix4::new_translation(&rotated_center)
+    pub fn get_center(&self) -> Vector3<T> {
+        self.get_top() / real::<T>(2.0)
     }
 
-    pub fn get_weight(&self) -> f32 {
+    pub fn get_weight(&self) -> T {
         self.density * self.size * self.size * self.size
     }
 
-    pub fn get_moment_of_interia(&self) -> Matrix3<f32> {
+    pub fn get_moment_of_interia(&self) -> Matrix3<T> {
         let weight = self.get_weight();
 
-        let base_i = weight * self.size * self.size / 6.0;
+        let base_i = weight * self.size * self.size / real(6.0);
 
         let mx = self.base_rotation.to_rotation_matrix().inverse() * Vector3::x();
         let my = self.base_rotation.to_rotation_matrix().inverse() * Vector3::y();
         let mz = self.base_rotation.to_rotation_matrix().inverse() * Vector3::z();
 
+        let vertex_shift = real::<T>(3.0) * weight * self.size * self.size / real(4.0);
+
         Matrix3::from_diagonal(&Vector3::new(
             base_i * mx.dot(&mx),
             base_i * my.dot(&my),
             base_i * mz.dot(&mz),
         )) + Matrix3::new(
-            3.0 * weight * self.size * self.size / 4.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            0.0,
-            3.0 * weight * self.size * self.size / 4.0,
+            vertex_shift,
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+            vertex_shift,
         )
     }
 }
@@ -2,7 +2,7 @@ use glium::glutin::surface::WindowSurface;
 use glium::{uniform, Display, DrawParameters, IndexBuffer, Program, Surface, VertexBuffer};
 use nalgebra::Matrix4;
 
-use crate::cube::Cube;
+use crate::rigid_body::RigidBody;
 use crate::vertex::Vertex;
 
 pub struct CubeDrawer {
@@ -77,7 +77,7 @@ impl CubeDrawer {
         target: &mut glium::Frame,
         perspective: &Matrix4<f32>,
         view: &Matrix4<f32>,
-        cube: &Cube,
+        body: &RigidBody<f32>,
         drawing_parameters: &DrawParameters,
     ) {
         target
@@ -88,7 +88,7 @@ impl CubeDrawer {
                 &uniform! {
                     perspective: perspective.data.0,
                     view: view.data.0,
-                    model: cube.get_model_matrix().data.0,
+                    model: body.get_model_matrix().data.0,
                 },
                 &drawing_parameters,
             )
@@ -0,0 +1,63 @@
+use derive_new::new;
+
+use crate::real::Real;
+
+#[derive(Debug, Clone, Copy, new)]
+pub struct Diagnostics<T: Real> {
+    pub kinetic_energy: T,
+    pub potential_energy: T,
+    pub vertical_angular_momentum: T,
+    pub angular_momentum_norm: T,
+    pub quaternion_norm_error: T,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Drift<T: Real> {
+    pub energy: T,
+    pub vertical_angular_momentum: T,
+    pub angular_momentum_norm: T,
+}
+
+impl<T: Real> Diagnostics<T> {
+    pub fn total_energy(&self) -> T {
+        self.kinetic_energy + self.potential_energy
+    }
+
+    pub fn drift_from(&self, initial: &Diagnostics<T>) -> Drift<T> {
+        Drift {
+            energy: Self::relative_drift(self.total_energy(), initial.total_energy()),
+            vertical_angular_momentum: Self::relative_drift(
+                self.vertical_angular_momentum,
+                initial.vertical_angular_momentum,
+            ),
+            angular_momentum_norm: Self::relative_drift(
+                self.angular_momentum_norm,
+                initial.angular_momentum_norm,
+            ),
+        }
+    }
+
+    pub fn cast<U: Real>(&self) -> Diagnostics<U> {
+        Diagnostics::new(
+            self.kinetic_energy.cast(),
+            self.potential_energy.cast(),
+            self.vertical_angular_momentum.cast(),
+            self.angular_momentum_norm.cast(),
+            self.quaternion_norm_error.cast(),
+        )
+    }
+
+    fn relative_drift(value: T, initial: T) -> T {
+        (value - initial) / initial.abs().max(T::default_epsilon())
+    }
+}
+
+impl<T: Real> Drift<T> {
+    pub fn cast<U: Real>(&self) -> Drift<U> {
+        Drift {
+            energy: self.energy.cast(),
+            vertical_angular_momentum: self.vertical_angular_momentum.cast(),
+            angular_momentum_norm: self.angular_momentum_norm.cast(),
+        }
+    }
+}
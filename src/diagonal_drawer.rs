@@ -4,7 +4,7 @@ use glium::{
 };
 use nalgebra::Matrix4;
 
-use crate::cube::Cube;
+use crate::rigid_body::RigidBody;
 use crate::vertex::Vertex;
 
 pub struct DiagonalDrawer {
@@ -46,10 +46,7 @@ impl DiagonalDrawer {
             program,
             vertex_buffer: VertexBuffer::new(
                 display,
-                &[
-                    Vertex::new([0.0, 0.0, 0.0]),
-                    Vertex::new([0.0, 3.0f32.sqrt(), 0.0]),
-                ],
+                &[Vertex::new([0.0, 0.0, 0.0]), Vertex::new([0.0, 1.0, 0.0])],
             )
             .unwrap(),
             index_buffer: IndexBuffer::new(
@@ -66,7 +63,7 @@ impl DiagonalDrawer {
         target: &mut glium::Frame,
         perspective: &Matrix4<f32>,
         view: &Matrix4<f32>,
-        cube: &Cube,
+        body: &RigidBody<f32>,
         drawing_parameters: &DrawParameters,
     ) {
         let mut drawing_parameters = drawing_parameters.clone();
@@ -80,7 +77,7 @@ impl DiagonalDrawer {
                 &uniform! {
                     perspective: perspective.data.0,
                     view: view.data.0,
-                    model: cube.get_diagonal_model_matrix().data.0,
+                    model: body.get_diagonal_model_matrix().data.0,
                 },
                 &drawing_parameters,
             )
@@ -0,0 +1,95 @@
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+
+use crate::{
+    integrator::{ErrorEstimate, Integrator, StepResult},
+    real::{real, Real},
+    simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+const A: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+const B: [f64; 7] = [
+    35.0 / 384.0,
+    0.0,
+    500.0 / 1113.0,
+    125.0 / 192.0,
+    -2187.0 / 6784.0,
+    11.0 / 84.0,
+    0.0,
+];
+const B_EMBEDDED: [f64; 7] = [
+    5179.0 / 57600.0,
+    0.0,
+    7571.0 / 16695.0,
+    393.0 / 640.0,
+    -92097.0 / 339200.0,
+    187.0 / 2100.0,
+    1.0 / 40.0,
+];
+
+pub struct DormandPrinceIntegrator;
+
+impl<T: Real> Integrator<T> for DormandPrinceIntegrator {
+    fn step(&mut self, dynamics: &TopDynamics<T>, state: &TopState<T>, h: T) -> StepResult<T> {
+        let q = *state.q.quaternion();
+        let w = state.w;
+        let mut k_q = [Quaternion::identity(); 7];
+        let mut k_w = [Vector3::zeros(); 7];
+
+        for i in 0..7 {
+            let stage_q = (0..i).fold(q, |sum, j| sum + k_q[j] * (h * real(A[i][j])));
+            let stage_w = (0..i).fold(w, |sum, j| sum + k_w[j] * (h * real(A[i][j])));
+            k_q[i] = TopDynamics::quaternion_derivative(&stage_q, &stage_w);
+            k_w[i] =
+                dynamics.angular_acceleration(&UnitQuaternion::from_quaternion(stage_q), &stage_w);
+        }
+
+        let new_q = (0..7).fold(q, |sum, i| sum + k_q[i] * (h * real(B[i])));
+        let new_w = (0..7).fold(w, |sum, i| sum + k_w[i] * (h * real(B[i])));
+        let error_q = (0..7).fold(Quaternion::default(), |sum, i| {
+            sum + k_q[i] * (h * real(B[i] - B_EMBEDDED[i]))
+        });
+        let error_w = (0..7).fold(Vector3::zeros(), |sum, i| {
+            sum + k_w[i] * (h * real(B[i] - B_EMBEDDED[i]))
+        });
+
+        StepResult::new(
+            TopState::new(UnitQuaternion::from_quaternion(new_q), new_w, state.t + h),
+            new_q.norm(),
+            Some(ErrorEstimate::new(error_q.coords, error_w)),
+        )
+    }
+
+    fn embedded_order(&self) -> Option<i32> {
+        Some(4)
+    }
+}
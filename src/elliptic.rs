@@ -0,0 +1,163 @@
+use std::f64::consts::PI;
+
+// Jacobi elliptic functions (sn, cn, dn) of parameter 0 <= m <= 1, by descending Landen
+// transformation (the `sncndn` routine of Numerical Recipes).
+pub fn jacobi(u: f64, m: f64) -> (f64, f64, f64) {
+    let mut emc = 1.0 - m;
+
+    if emc == 0.0 {
+        let cn = 1.0 / u.cosh();
+        return (u.tanh(), cn, cn);
+    }
+
+    let mut em = [0.0; 16];
+    let mut en = [0.0; 16];
+    let mut a = 1.0;
+    let mut c = 1.0;
+    let mut dn = 1.0;
+    let mut last = 0;
+
+    for i in 0..16 {
+        last = i;
+        em[i] = a;
+        emc = emc.sqrt();
+        en[i] = emc;
+        c = 0.5 * (a + emc);
+        if (a - emc).abs() <= 1e-9 * a {
+            break;
+        }
+        emc *= a;
+        a = c;
+    }
+
+    let mut sn = (u * c).sin();
+    let mut cn = (u * c).cos();
+
+    if sn != 0.0 {
+        a = cn / sn;
+        c *= a;
+        for i in (0..=last).rev() {
+            let b = em[i];
+            a *= c;
+            c *= dn;
+            dn = (en[i] + a) / (b + a);
+            a = c / b;
+        }
+        a = 1.0 / (c * c + 1.0).sqrt();
+        sn = if sn >= 0.0 { a } else { -a };
+        cn = c * sn;
+    }
+
+    (sn, cn, dn)
+}
+
+// Each duplication shrinks the spread of the arguments fourfold, so converging arguments
+// need far fewer; the cap only stops a run that cannot converge.
+const CARLSON_ITERATIONS: usize = 64;
+
+// Carlson's symmetric integral R_F by duplication. NaN outside its domain of non-negative,
+// finite arguments with at most one zero.
+fn carlson_rf(x: f64, y: f64, z: f64) -> f64 {
+    let arguments = [x, y, z];
+    let valid = arguments.iter().all(|a| a.is_finite() && *a >= 0.0)
+        && arguments.iter().filter(|a| **a == 0.0).count() <= 1;
+    if !valid {
+        return f64::NAN;
+    }
+
+    let (mut x, mut y, mut z) = (x, y, z);
+
+    for _ in 0..CARLSON_ITERATIONS {
+        let mean = (x + y + z) / 3.0;
+        let (dx, dy, dz) = ((mean - x) / mean, (mean - y) / mean, (mean - z) / mean);
+
+        if dx.abs().max(dy.abs()).max(dz.abs()) < 0.0025 {
+            let e2 = dx * dy - dz * dz;
+            let e3 = dx * dy * dz;
+            return (1.0 + (e2 / 24.0 - 0.1 - 3.0 * e3 / 44.0) * e2 + e3 / 14.0) / mean.sqrt();
+        }
+
+        let (sx, sy, sz) = (x.sqrt(), y.sqrt(), z.sqrt());
+        let lambda = sx * (sy + sz) + sy * sz;
+        x = 0.25 * (x + lambda);
+        y = 0.25 * (y + lambda);
+        z = 0.25 * (z + lambda);
+    }
+
+    f64::NAN
+}
+
+pub fn complete_first_kind(m: f64) -> f64 {
+    carlson_rf(0.0, 1.0 - m, 1.0)
+}
+
+// Incomplete integral of the first kind for any amplitude, so that sn(F(φ, m), m) = sin φ
+// and cn(F(φ, m), m) = cos φ.
+pub fn incomplete_first_kind(amplitude: f64, m: f64) -> f64 {
+    let turns = (amplitude / PI).round();
+    let reduced = amplitude - turns * PI;
+    let (sin, cos) = reduced.sin_cos();
+
+    2.0 * turns * complete_first_kind(m) + sin * carlson_rf(cos * cos, 1.0 - m * sin * sin, 1.0)
+}
+
+const GAUSS_NODES: [f64; 4] = [
+    0.183_434_642_495_649_8,
+    0.525_532_409_916_329,
+    0.796_666_477_413_626_7,
+    0.960_289_856_497_536_3,
+];
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.362_683_783_378_362,
+    0.313_706_645_877_887_3,
+    0.222_381_034_453_374_5,
+    0.101_228_536_290_376_3,
+];
+
+// Composite 8-point Gauss–Legendre with panels no longer than `panel`.
+pub fn integrate(f: impl Fn(f64) -> f64, from: f64, to: f64, panel: f64) -> f64 {
+    let panels = ((to - from).abs() / panel).ceil().max(1.0);
+    // A non-finite interval would otherwise ask for an unbounded number of panels.
+    if !panels.is_finite() {
+        return f64::NAN;
+    }
+    let width = (to - from) / panels;
+    let half = 0.5 * width;
+
+    (0..panels as usize)
+        .map(|i| {
+            let middle = from + (i as f64 + 0.5) * width;
+            GAUSS_NODES
+                .iter()
+                .zip(GAUSS_WEIGHTS)
+                .map(|(&node, weight)| weight * (f(middle - half * node) + f(middle + half * node)))
+                .sum::<f64>()
+                * half
+        })
+        .sum()
+}
+
+// Integral from 0 to t of a function with the given period, reusing the integral over a
+// whole period so the cost does not grow with t.
+pub fn integrate_periodic(f: impl Fn(f64) -> f64, t: f64, period: f64, per_period: f64) -> f64 {
+    let periods = (t / period).floor();
+
+    periods * per_period + integrate(f, 0.0, t - periods * period, period / 256.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete_first_kind, incomplete_first_kind};
+
+    #[test]
+    fn non_finite_arguments_give_nan() {
+        assert!(complete_first_kind(f64::NAN).is_nan());
+        assert!(complete_first_kind(f64::NEG_INFINITY).is_nan());
+        assert!(incomplete_first_kind(f64::INFINITY, 0.5).is_nan());
+    }
+
+    #[test]
+    fn complete_integral_of_a_circle() {
+        assert!((complete_first_kind(0.0) - std::f64::consts::FRAC_PI_2).abs() < 1e-14);
+    }
+}
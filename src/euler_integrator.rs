@@ -0,0 +1,28 @@
+use nalgebra::UnitQuaternion;
+
+use crate::{
+    integrator::{Integrator, StepResult},
+    real::Real,
+    simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+pub struct EulerIntegrator;
+
+impl<T: Real> Integrator<T> for EulerIntegrator {
+    fn step(&mut self, dynamics: &TopDynamics<T>, state: &TopState<T>, h: T) -> StepResult<T> {
+        let q = *state.q.quaternion();
+
+        let new_q = q + TopDynamics::quaternion_derivative(&q, &state.w) * h;
+
+        StepResult::new(
+            TopState::new(
+                UnitQuaternion::from_quaternion(new_q),
+                state.w + dynamics.angular_acceleration(&state.q, &state.w) * h,
+                state.t + h,
+            ),
+            new_q.norm(),
+            None,
+        )
+    }
+}
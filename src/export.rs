@@ -0,0 +1,295 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::Path,
+};
+
+use serde_json::{Map, Value};
+
+use crate::{
+    diagnostics::Diagnostics, history::History, rigid_body::RigidBody, simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Time,
+    Quaternion,
+    AngularVelocity,
+    TipPosition,
+    Energy,
+    AngularMomentum,
+    Drift,
+    QuaternionNormError,
+}
+
+// Fields of a full row, in this order; each column is a contiguous range of them.
+const FIELDS: [&str; 19] = [
+    "t",
+    "qw",
+    "qi",
+    "qj",
+    "qk",
+    "wx",
+    "wy",
+    "wz",
+    "tip_x",
+    "tip_y",
+    "tip_z",
+    "energy",
+    "lx",
+    "ly",
+    "lz",
+    "energy_drift",
+    "vertical_angular_momentum_drift",
+    "angular_momentum_norm_drift",
+    "quaternion_norm_error",
+];
+
+impl Column {
+    pub const ALL: [Column; 8] = [
+        Column::Time,
+        Column::Quaternion,
+        Column::AngularVelocity,
+        Column::TipPosition,
+        Column::Energy,
+        Column::AngularMomentum,
+        Column::Drift,
+        Column::QuaternionNormError,
+    ];
+
+    fn fields(&self) -> Range<usize> {
+        match self {
+            Column::Time => 0..1,
+            Column::Quaternion => 1..5,
+            Column::AngularVelocity => 5..8,
+            Column::TipPosition => 8..11,
+            Column::Energy => 11..12,
+            Column::AngularMomentum => 12..15,
+            Column::Drift => 15..18,
+            Column::QuaternionNormError => 18..19,
+        }
+    }
+}
+
+impl Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Column::Time => write!(f, "time"),
+            Column::Quaternion => write!(f, "quaternion"),
+            Column::AngularVelocity => write!(f, "angular velocity (body)"),
+            Column::TipPosition => write!(f, "tip position"),
+            Column::Energy => write!(f, "energy"),
+            Column::AngularMomentum => write!(f, "angular momentum (world)"),
+            Column::Drift => write!(f, "relative drift (energy, L·ĝ, |L|)"),
+            Column::QuaternionNormError => write!(f, "quaternion norm error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    JsonLines,
+    Npy,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::Csv,
+        ExportFormat::JsonLines,
+        ExportFormat::Npy,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::JsonLines => "jsonl",
+            ExportFormat::Npy => "npy",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::JsonLines => write!(f, "JSON Lines"),
+            ExportFormat::Npy => write!(f, "NumPy .npy"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    pub columns: Vec<Column>,
+    // Every n-th sample of the history is written.
+    pub decimation: usize,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Csv,
+            columns: Column::ALL.to_vec(),
+            decimation: 1,
+        }
+    }
+}
+
+// Derived quantities are computed from the body the run used, with gravity as it was set
+// at each sample. Drifts are relative to the first sample and, like the simulator's, start
+// over wherever gravity was toggled.
+pub fn export(
+    path: impl AsRef<Path>,
+    settings: &ExportSettings,
+    body: &RigidBody<f64>,
+    history: &History,
+) -> io::Result<()> {
+    let fields: Vec<usize> = Column::ALL
+        .iter()
+        .filter(|column| settings.columns.contains(column))
+        .flat_map(Column::fields)
+        .collect();
+    let mut dynamics = TopDynamics::new(body);
+    let mut baseline = None;
+    let mut rows = Vec::new();
+    for (index, (sample, norm_error)) in history
+        .samples()
+        .iter()
+        .zip(history.quaternion_norm_errors())
+        .enumerate()
+    {
+        let gravity = gravity_at(history, sample.t);
+        let changed = dynamics.gravity() != gravity;
+        dynamics.set_gravity(gravity);
+        let diagnostics = Diagnostics {
+            quaternion_norm_error: *norm_error,
+            ..dynamics.diagnostics(sample, 1.0)
+        };
+        if baseline.is_none() || changed {
+            baseline = Some(diagnostics);
+        }
+
+        if index % settings.decimation.max(1) == 0 {
+            let row = full_row(sample, body, &diagnostics, &baseline.unwrap());
+            rows.push(fields.iter().map(|&field| row[field]).collect::<Vec<f64>>());
+        }
+    }
+    let names: Vec<&str> = fields.iter().map(|&field| FIELDS[field]).collect();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    match settings.format {
+        ExportFormat::Csv => write_csv(&mut writer, &names, &rows)?,
+        ExportFormat::JsonLines => write_json_lines(&mut writer, &names, &rows)?,
+        ExportFormat::Npy => write_npy(&mut writer, &names, &rows)?,
+    }
+
+    writer.flush()
+}
+
+fn gravity_at(history: &History, t: f64) -> bool {
+    history
+        .gravity_changes()
+        .iter()
+        .take_while(|(change, _)| *change <= t)
+        .last()
+        .is_none_or(|(_, gravity)| *gravity)
+}
+
+fn full_row(
+    state: &TopState<f64>,
+    body: &RigidBody<f64>,
+    diagnostics: &Diagnostics<f64>,
+    baseline: &Diagnostics<f64>,
+) -> [f64; 19] {
+    let q = state.q.quaternion();
+    let tip = state.q * body.tip();
+    let angular_momentum = state.q * (body.moment_of_interia() * state.w);
+    let drift = diagnostics.drift_from(baseline);
+
+    [
+        state.t,
+        q.w,
+        q.i,
+        q.j,
+        q.k,
+        state.w.x,
+        state.w.y,
+        state.w.z,
+        tip.x,
+        tip.y,
+        tip.z,
+        diagnostics.total_energy(),
+        angular_momentum.x,
+        angular_momentum.y,
+        angular_momentum.z,
+        drift.energy,
+        drift.vertical_angular_momentum,
+        drift.angular_momentum_norm,
+        diagnostics.quaternion_norm_error,
+    ]
+}
+
+fn write_csv(writer: &mut impl Write, names: &[&str], rows: &[Vec<f64>]) -> io::Result<()> {
+    writeln!(writer, "{}", names.join(","))?;
+
+    for row in rows {
+        let values: Vec<String> = row.iter().map(f64::to_string).collect();
+        writeln!(writer, "{}", values.join(","))?;
+    }
+
+    Ok(())
+}
+
+fn write_json_lines(writer: &mut impl Write, names: &[&str], rows: &[Vec<f64>]) -> io::Result<()> {
+    for row in rows {
+        let object: Map<String, Value> = names
+            .iter()
+            .zip(row)
+            .map(|(name, value)| (name.to_string(), Value::from(*value)))
+            .collect();
+        serde_json::to_writer(&mut *writer, &object)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+// A one-dimensional structured array of little-endian doubles, one named field per column,
+// so `numpy.load` keeps the column names.
+fn write_npy(writer: &mut impl Write, names: &[&str], rows: &[Vec<f64>]) -> io::Result<()> {
+    let description: Vec<String> = names
+        .iter()
+        .map(|name| format!("('{}', '<f8')", name))
+        .collect();
+    let mut header = format!(
+        "{{'descr': [{}], 'fortran_order': False, 'shape': ({},), }}",
+        description.join(", "),
+        rows.len()
+    );
+    // Magic, version and length take 10 bytes; the header ends in a newline and pads the
+    // whole preamble to a multiple of 64.
+    let padding = (64 - (10 + header.len() + 1) % 64) % 64;
+    header.extend(std::iter::repeat_n(' ', padding));
+    header.push('\n');
+
+    writer.write_all(b"\x93NUMPY\x01\x00")?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+
+    for value in rows.iter().flatten() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::{path::Path, sync::Mutex};
+
+use egui::{Color32, Context, DragValue, Ui, Widget};
+
+use crate::{
+    export::{export, Column, ExportFormat, ExportSettings},
+    history::History,
+    simulation_parameters::SimulationParameters,
+};
+
+#[derive(Default)]
+pub struct ExportWindow {
+    open: bool,
+    settings: ExportSettings,
+    path: String,
+    message: Option<Result<String, String>>,
+}
+
+impl ExportWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    // `run` is the run the history belongs to, None when there is nothing to export. The
+    // history is shared with the simulation thread, so it is only locked to read its length
+    // and to take a copy for the export.
+    pub fn show(
+        &mut self,
+        ctx: &Context,
+        run: Option<&SimulationParameters>,
+        history: &Mutex<History>,
+    ) {
+        let mut open = self.open;
+
+        egui::Window::new("export data")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, run, history));
+
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut Ui, run: Option<&SimulationParameters>, history: &Mutex<History>) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("export format")
+                .selected_text(self.settings.format.to_string())
+                .show_ui(ui, |ui| {
+                    for format in ExportFormat::ALL {
+                        if ui
+                            .selectable_value(&mut self.settings.format, format, format.to_string())
+                            .changed()
+                            && !self.path.is_empty()
+                        {
+                            self.path = Path::new(&self.path)
+                                .with_extension(format.extension())
+                                .display()
+                                .to_string();
+                        }
+                    }
+                });
+
+            ui.label("format");
+        });
+
+        for column in Column::ALL {
+            let mut selected = self.settings.columns.contains(&column);
+            if ui.checkbox(&mut selected, column.to_string()).changed() {
+                self.settings.columns.retain(|other| *other != column);
+                if selected {
+                    self.settings.columns.push(column);
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            DragValue::new(&mut self.settings.decimation)
+                .clamp_range(1..=10_000)
+                .ui(ui);
+
+            ui.label("decimation");
+        });
+
+        let samples = history.lock().unwrap().samples().len();
+        ui.label(format!(
+            "{} of {} samples",
+            samples.div_ceil(self.settings.decimation.max(1)),
+            samples
+        ));
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.path);
+
+            let exportable = run.is_some() && samples > 0 && !self.settings.columns.is_empty();
+            if ui
+                .add_enabled(exportable, egui::Button::new("export"))
+                .clicked()
+            {
+                if let Some(run) = run {
+                    let history = history.lock().unwrap().clone();
+                    self.message = Some(
+                        export(&self.path, &self.settings, &run.build_body(), &history)
+                            .map(|_| format!("wrote {}", self.path))
+                            .map_err(|error| error.to_string()),
+                    );
+                }
+            }
+        });
+
+        match &self.message {
+            Some(Ok(message)) => {
+                ui.label(message);
+            }
+            Some(Err(error)) => {
+                ui.colored_label(Color32::RED, error);
+            }
+            None => {}
+        }
+    }
+}
@@ -4,7 +4,7 @@ use glium::{
 };
 use nalgebra::Matrix4;
 
-use crate::cube::Cube;
+use crate::rigid_body::RigidBody;
 use crate::vertex::Vertex;
 
 pub struct GravityVectorDrawer {
@@ -63,7 +63,7 @@ impl GravityVectorDrawer {
         target: &mut glium::Frame,
         perspective: &Matrix4<f32>,
         view: &Matrix4<f32>,
-        cube: &Cube,
+        body: &RigidBody<f32>,
         drawing_parameters: &DrawParameters,
     ) {
         let mut drawing_parameters = drawing_parameters.clone();
@@ -77,7 +77,7 @@ impl GravityVectorDrawer {
                 &uniform! {
                     perspective: perspective.data.0,
                     view: view.data.0,
-                    model: cube.get_gravity_vector_model_matrix().data.0,
+                    model: body.get_gravity_vector_model_matrix().data.0,
                 },
                 &drawing_parameters,
             )
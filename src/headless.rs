@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use derive_new::new;
+use nalgebra::Vector3;
+use serde::Serialize;
+
+use crate::{
+    history::History,
+    precession_analyser::PrecessionAnalyser,
+    real::{Precision, Real},
+    simulation::TopState,
+    simulation_parameters::SimulationParameters,
+};
+
+#[derive(Debug, Clone, Copy, new)]
+pub struct HeadlessSettings {
+    pub end_time: f64,
+    pub history_decimation: u64,
+    // Largest relative energy drift before the run counts as diverged, None to not check.
+    pub energy_tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Divergence {
+    NonFinite,
+    EnergyDrift,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Divergence::NonFinite => write!(f, "the state is no longer finite"),
+            Divergence::EnergyDrift => write!(f, "the energy drift exceeds the tolerance"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    // Simulated time reached, earlier than the end time when the run diverged.
+    pub simulated_time: f64,
+    pub steps: u64,
+    pub divergence: Option<Divergence>,
+    // Largest relative drifts from the initial values, in absolute value.
+    pub max_energy_drift: f64,
+    pub max_vertical_angular_momentum_drift: f64,
+    // Largest angle between the tip and the vertical through the pivot, in radians.
+    pub max_tilt: f64,
+    // First time the tip dropped below the horizontal plane through the pivot.
+    pub fall_time: Option<f64>,
+    pub precession_period: Option<f64>,
+    pub nutation_amplitude: Option<f64>,
+    pub final_state: TopState<f64>,
+}
+
+// Runs a simulation to the end time as fast as possible, without a clock or a display.
+// A diverged run stops at the step where it was detected.
+pub fn run_headless(
+    parameters: &SimulationParameters,
+    settings: &HeadlessSettings,
+) -> (History, RunSummary) {
+    let parameters = SimulationParameters {
+        analytic_reference: false,
+        ..parameters.clone()
+    };
+
+    match parameters.precision {
+        Precision::Single => run_with::<f32>(&parameters, settings),
+        Precision::Double => run_with::<f64>(&parameters, settings),
+    }
+}
+
+fn run_with<T: Real>(
+    parameters: &SimulationParameters,
+    settings: &HeadlessSettings,
+) -> (History, RunSummary) {
+    let mut simulator = parameters.build_simulator::<T>();
+    let body = parameters.build_body::<f64>();
+    let mut analyser = PrecessionAnalyser::new(&body);
+    let mut history = History::new(settings.history_decimation);
+    history.record_gravity(simulator.state().t.cast(), parameters.gravity);
+
+    let end_step = (settings.end_time / parameters.integration_step).round() as u64;
+    let tilt = |state: &TopState<f64>| (state.q * body.tip()).angle(&Vector3::y());
+    let initial = simulator.state().cast::<f64>();
+    history.record(
+        simulator.steps(),
+        &initial,
+        simulator.diagnostics().quaternion_norm_error.cast(),
+    );
+
+    let mut summary = RunSummary {
+        simulated_time: initial.t,
+        steps: simulator.steps(),
+        divergence: None,
+        max_energy_drift: 0.0,
+        max_vertical_angular_momentum_drift: 0.0,
+        max_tilt: tilt(&initial),
+        fall_time: None,
+        precession_period: None,
+        nutation_amplitude: None,
+        final_state: initial,
+    };
+
+    while simulator.steps() < end_step {
+        simulator.advance_step();
+
+        let state = simulator.state().cast::<f64>();
+        let diagnostics = simulator.diagnostics().cast::<f64>();
+        let drift = simulator.drift().cast::<f64>();
+        history.record(simulator.steps(), &state, diagnostics.quaternion_norm_error);
+        summary.simulated_time = state.t;
+        summary.steps = simulator.steps();
+        summary.final_state = state;
+
+        let finite = state
+            .q
+            .coords
+            .iter()
+            .chain(state.w.iter())
+            .all(|x| x.is_finite());
+        if !finite {
+            summary.divergence = Some(Divergence::NonFinite);
+            break;
+        }
+
+        analyser.record(&state, parameters.gravity);
+        summary.max_energy_drift = summary.max_energy_drift.max(drift.energy.abs());
+        summary.max_vertical_angular_momentum_drift = summary
+            .max_vertical_angular_momentum_drift
+            .max(drift.vertical_angular_momentum.abs());
+        summary.max_tilt = summary.max_tilt.max(tilt(&state));
+        if summary.fall_time.is_none() && simulator.tip_position().y < T::zero() {
+            summary.fall_time = Some(state.t);
+        }
+
+        if settings
+            .energy_tolerance
+            .is_some_and(|tolerance| summary.max_energy_drift > tolerance)
+        {
+            summary.divergence = Some(Divergence::EnergyDrift);
+            break;
+        }
+    }
+
+    let readout = analyser.readout();
+    summary.precession_period = readout.precession_period;
+    summary.nutation_amplitude = readout.nutation_amplitude;
+
+    (history, summary)
+}
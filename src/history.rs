@@ -0,0 +1,117 @@
+use crate::simulation::TopState;
+
+pub const HISTORY_CAPACITY: usize = 100_000;
+
+// Every `decimation`-th simulated state of a run. When full, every other sample is dropped
+// and the stride doubles, so the history always spans the whole run.
+#[derive(Debug, Clone)]
+pub struct History {
+    decimation: u64,
+    stride: u64,
+    samples: Vec<TopState<f64>>,
+    // |q| − 1 of each sample before the integrator renormalised it, NaN where unknown.
+    quaternion_norm_errors: Vec<f64>,
+    // Gravity setting from each of these times on, starting with the one the run began with.
+    gravity_changes: Vec<(f64, bool)>,
+}
+
+impl History {
+    pub fn new(decimation: u64) -> Self {
+        let decimation = decimation.max(1);
+
+        Self {
+            decimation,
+            stride: decimation,
+            samples: Vec::new(),
+            quaternion_norm_errors: Vec::new(),
+            gravity_changes: Vec::new(),
+        }
+    }
+
+    pub fn from_parts(
+        samples: Vec<TopState<f64>>,
+        quaternion_norm_errors: Vec<f64>,
+        gravity_changes: Vec<(f64, bool)>,
+    ) -> Self {
+        Self {
+            samples,
+            quaternion_norm_errors,
+            gravity_changes,
+            ..Self::new(1)
+        }
+    }
+
+    pub fn samples(&self) -> &[TopState<f64>] {
+        &self.samples
+    }
+
+    pub fn quaternion_norm_errors(&self) -> &[f64] {
+        &self.quaternion_norm_errors
+    }
+
+    pub fn gravity_changes(&self) -> &[(f64, bool)] {
+        &self.gravity_changes
+    }
+
+    pub fn record_gravity(&mut self, t: f64, gravity: bool) {
+        self.gravity_changes.push((t, gravity));
+    }
+
+    pub fn set_decimation(&mut self, decimation: u64) {
+        self.decimation = decimation.max(1);
+        self.stride = self.stride.max(self.decimation);
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.quaternion_norm_errors.clear();
+        self.gravity_changes.clear();
+        self.stride = self.decimation;
+    }
+
+    // Drops `t` and everything after, e.g. when a run branches off at that instant.
+    pub fn truncate_from(&mut self, t: f64) {
+        let kept = self.samples.partition_point(|sample| sample.t < t);
+        self.samples.truncate(kept);
+        self.quaternion_norm_errors.truncate(kept);
+        self.gravity_changes.retain(|(change, _)| *change < t);
+    }
+
+    pub fn record(&mut self, step: u64, state: &TopState<f64>, quaternion_norm_error: f64) {
+        if !step.is_multiple_of(self.stride) {
+            return;
+        }
+
+        self.samples.push(*state);
+        self.quaternion_norm_errors.push(quaternion_norm_error);
+
+        if self.samples.len() > HISTORY_CAPACITY {
+            let mut index = 0;
+            self.samples.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            let mut index = 0;
+            self.quaternion_norm_errors.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.stride *= 2;
+        }
+    }
+
+    pub fn start(&self) -> Option<f64> {
+        self.samples.first().map(|sample| sample.t)
+    }
+
+    pub fn end(&self) -> Option<f64> {
+        self.samples.last().map(|sample| sample.t)
+    }
+
+    // The last sample at or before `t`.
+    pub fn at(&self, t: f64) -> Option<&TopState<f64>> {
+        let index = self.samples.partition_point(|sample| sample.t <= t);
+
+        self.samples.get(index.saturating_sub(1))
+    }
+}
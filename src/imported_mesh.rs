@@ -0,0 +1,412 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use derive_getters::Getters;
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    mesh::Mesh,
+    real::{real, Real},
+    shape::MassProperties,
+    vertex::Vertex,
+};
+
+const VOLUME_TOLERANCE: f64 = 1e-9;
+
+#[derive(Debug)]
+pub enum MeshError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+    UnsupportedFormat(String),
+    Empty,
+    IndexOutOfRange,
+    // The enclosed volume is too small to derive mass properties from, e.g. a flat mesh.
+    Degenerate,
+}
+
+impl Display for MeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshError::Io(error) => write!(f, "{}", error),
+            MeshError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            MeshError::UnsupportedFormat(extension) => {
+                write!(f, "unsupported mesh format '{}'", extension)
+            }
+            MeshError::Empty => write!(f, "mesh has no triangles"),
+            MeshError::IndexOutOfRange => write!(f, "triangle index out of range"),
+            MeshError::Degenerate => write!(f, "mesh encloses no volume"),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+impl From<std::io::Error> for MeshError {
+    fn from(error: std::io::Error) -> Self {
+        MeshError::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct ImportedMesh {
+    path: PathBuf,
+    mesh: Mesh,
+    #[getter(copy)]
+    watertight: bool,
+}
+
+impl PartialEq for ImportedMesh {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+// The geometry is stored with the path, so configurations and recordings of a mesh body load
+// on machines without the file. A bare path, as written before, is reloaded from disk.
+#[derive(Serialize, Deserialize)]
+struct StoredMesh {
+    path: PathBuf,
+    positions: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredMeshOrPath {
+    Mesh(StoredMesh),
+    Path(PathBuf),
+}
+
+impl Serialize for ImportedMesh {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredMesh {
+            path: self.path.clone(),
+            positions: self.mesh.vertices().iter().map(|v| *v.position()).collect(),
+            indices: self.mesh.indices().clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ImportedMesh {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (path, loaded) = match StoredMeshOrPath::deserialize(deserializer)? {
+            StoredMeshOrPath::Mesh(stored) => {
+                let triangles = stored
+                    .indices
+                    .chunks_exact(3)
+                    .map(|t| [t[0], t[1], t[2]])
+                    .collect();
+                let loaded = Self::from_triangles(&stored.path, stored.positions, triangles);
+                (stored.path, loaded)
+            }
+            StoredMeshOrPath::Path(path) => {
+                let loaded = ImportedMesh::load(&path);
+                (path, loaded)
+            }
+        };
+
+        loaded.map_err(|error| serde::de::Error::custom(format!("{}: {}", path.display(), error)))
+    }
+}
+
+impl ImportedMesh {
+    // Mesh coordinates are used as the body frame, so the pivot is the file's origin.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MeshError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let (positions, triangles) = match extension.as_str() {
+            "obj" => parse_obj(&fs::read_to_string(path)?)?,
+            "stl" => parse_stl(&fs::read(path)?)?,
+            _ => return Err(MeshError::UnsupportedFormat(extension)),
+        };
+
+        Self::from_triangles(path, positions, triangles)
+    }
+
+    fn from_triangles(
+        path: &Path,
+        positions: Vec<[f32; 3]>,
+        triangles: Vec<[u32; 3]>,
+    ) -> Result<Self, MeshError> {
+        if triangles.is_empty() {
+            return Err(MeshError::Empty);
+        }
+        if triangles
+            .iter()
+            .flatten()
+            .any(|&i| i as usize >= positions.len())
+        {
+            return Err(MeshError::IndexOutOfRange);
+        }
+
+        let watertight = is_watertight(&triangles);
+        let mut mesh = Mesh::new(
+            positions.into_iter().map(Vertex::new).collect(),
+            triangles.into_iter().flatten().collect(),
+        );
+
+        // Meshes exported with inward facing triangles have a negative signed volume.
+        let volume = signed_volume(&mesh);
+        if volume < 0.0 {
+            mesh.flip();
+        }
+
+        // The centre of mass divides by the volume, so a flat or badly open mesh would give
+        // a NaN body; the volume is compared with the cube of the bounding box diagonal.
+        let positions = mesh
+            .vertices()
+            .iter()
+            .map(|v| Vector3::from(*v.position()).cast::<f64>());
+        let (min, max) = positions.fold(
+            (Vector3::repeat(f64::MAX), Vector3::repeat(f64::MIN)),
+            |(min, max), p| (min.inf(&p), max.sup(&p)),
+        );
+        let diagonal = (max - min).norm();
+        if volume.is_nan() || volume.abs() <= VOLUME_TOLERANCE * diagonal.powi(3) {
+            return Err(MeshError::Degenerate);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mesh,
+            watertight,
+        })
+    }
+
+    pub fn mass_properties<T: Real>(&self, density: T) -> MassProperties<T> {
+        let mut volume = T::zero();
+        let mut first_moment = Vector3::zeros();
+        let mut second_moment = Matrix3::zeros();
+
+        for [a, b, c] in self.triangles::<T>() {
+            // Each triangle spans a tetrahedron with the origin; its signed volume,
+            // centroid and covariance integrate the solid by the divergence theorem.
+            let determinant = a.dot(&b.cross(&c));
+            let sum = a + b + c;
+
+            volume += determinant / real(6.0);
+            first_moment += sum * (determinant / real(24.0));
+            second_moment +=
+                (a * a.transpose() + b * b.transpose() + c * c.transpose() + sum * sum.transpose())
+                    * (determinant / real(120.0));
+        }
+
+        let mass = volume * density;
+        let center = first_moment / volume;
+        let inertia_at_origin =
+            (Matrix3::identity() * second_moment.trace() - second_moment) * density;
+
+        MassProperties::new(
+            mass,
+            center,
+            inertia_at_origin
+                - (Matrix3::identity() * center.norm_squared() - center * center.transpose())
+                    * mass,
+        )
+    }
+
+    pub fn tip<T: Real>(&self) -> Vector3<T> {
+        let center = self.mass_properties(T::one()).center;
+        let direction = center
+            .try_normalize(T::default_epsilon())
+            .unwrap_or_else(Vector3::y);
+        let reach = self
+            .mesh
+            .vertices()
+            .iter()
+            .map(|v| Self::position::<T>(v).dot(&direction))
+            .fold(T::zero(), T::max);
+
+        direction * reach
+    }
+
+    fn position<T: Real>(vertex: &Vertex) -> Vector3<T> {
+        Vector3::from(*vertex.position()).map(|x| real(x as f64))
+    }
+
+    fn triangles<T: Real>(&self) -> impl Iterator<Item = [Vector3<T>; 3]> + '_ {
+        let vertices = self.mesh.vertices();
+
+        self.mesh.indices().chunks_exact(3).map(move |t| {
+            [
+                Self::position(&vertices[t[0] as usize]),
+                Self::position(&vertices[t[1] as usize]),
+                Self::position(&vertices[t[2] as usize]),
+            ]
+        })
+    }
+}
+
+fn signed_volume(mesh: &Mesh) -> f64 {
+    mesh.indices()
+        .chunks_exact(3)
+        .map(|t| {
+            let [a, b, c] = [t[0], t[1], t[2]]
+                .map(|i| Vector3::from(*mesh.vertices()[i as usize].position()).cast::<f64>());
+            a.dot(&b.cross(&c)) / 6.0
+        })
+        .sum()
+}
+
+// A closed, consistently oriented surface uses every directed edge exactly once and
+// always together with its reverse.
+fn is_watertight(triangles: &[[u32; 3]]) -> bool {
+    let mut edges = HashMap::new();
+
+    for t in triangles {
+        for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            *edges.entry((a, b)).or_insert(0) += 1;
+        }
+    }
+
+    edges
+        .iter()
+        .all(|(&(a, b), &count)| count == 1 && edges.get(&(b, a)) == Some(&1))
+}
+
+type Triangles = (Vec<[f32; 3]>, Vec<[u32; 3]>);
+
+fn parse_obj(source: &str) -> Result<Triangles, MeshError> {
+    let mut positions = Vec::new();
+    let mut triangles = Vec::new();
+
+    for (number, line) in source.lines().enumerate() {
+        let error = |message: &str| MeshError::Parse {
+            line: number + 1,
+            message: message.to_string(),
+        };
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let coordinates = tokens
+                    .take(3)
+                    .map(|t| t.parse::<f32>().map_err(|_| error("invalid vertex")))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if coordinates.len() != 3 {
+                    return Err(error("vertex needs three coordinates"));
+                }
+                positions.push([coordinates[0], coordinates[1], coordinates[2]]);
+            }
+            Some("f") => {
+                let face = tokens
+                    .map(|t| {
+                        let index = t
+                            .split('/')
+                            .next()
+                            .and_then(|i| i.parse::<i64>().ok())
+                            .ok_or_else(|| error("invalid face index"))?;
+                        let index = if index < 0 {
+                            positions.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        if index < 0 || index as usize >= positions.len() {
+                            return Err(error("face index out of range"));
+                        }
+                        Ok(index as u32)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if face.len() < 3 {
+                    return Err(error("face needs at least three vertices"));
+                }
+                for i in 1..face.len() - 1 {
+                    triangles.push([face[0], face[i], face[i + 1]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((positions, triangles))
+}
+
+fn parse_stl(bytes: &[u8]) -> Result<Triangles, MeshError> {
+    let binary_count = bytes
+        .get(80..84)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+
+    let corners = match binary_count {
+        Some(count) if bytes.len() == 84 + 50 * count => (0..count)
+            .flat_map(|i| {
+                let facet = &bytes[84 + 50 * i..84 + 50 * (i + 1)];
+                (1..4).map(move |corner| {
+                    let mut position = [0f32; 3];
+                    for (axis, value) in position.iter_mut().enumerate() {
+                        let offset = 12 * corner + 4 * axis;
+                        *value = f32::from_le_bytes([
+                            facet[offset],
+                            facet[offset + 1],
+                            facet[offset + 2],
+                            facet[offset + 3],
+                        ]);
+                    }
+                    position
+                })
+            })
+            .collect(),
+        _ => parse_ascii_stl(&String::from_utf8_lossy(bytes))?,
+    };
+
+    // STL stores every triangle separately, so coincident corners are welded to be
+    // able to tell whether the surface is closed.
+    let mut welded = HashMap::new();
+    let mut positions = Vec::new();
+    let indices = corners
+        .iter()
+        .map(|p: &[f32; 3]| {
+            *welded.entry(p.map(f32::to_bits)).or_insert_with(|| {
+                positions.push(*p);
+                positions.len() as u32 - 1
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok((
+        positions,
+        indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect(),
+    ))
+}
+
+fn parse_ascii_stl(source: &str) -> Result<Vec<[f32; 3]>, MeshError> {
+    let mut corners = Vec::new();
+
+    for (number, line) in source.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        if tokens.next() == Some("vertex") {
+            let coordinates = tokens
+                .take(3)
+                .map(|t| t.parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .ok()
+                .filter(|c| c.len() == 3)
+                .ok_or_else(|| MeshError::Parse {
+                    line: number + 1,
+                    message: "invalid vertex".to_string(),
+                })?;
+            corners.push([coordinates[0], coordinates[1], coordinates[2]]);
+        }
+    }
+
+    if corners.len() % 3 != 0 {
+        return Err(MeshError::Parse {
+            line: source.lines().count(),
+            message: "incomplete facet".to_string(),
+        });
+    }
+
+    Ok(corners)
+}
@@ -0,0 +1,241 @@
+use std::fmt::Display;
+
+use nalgebra::{Quaternion, Unit, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    real::{real, Real},
+    simulation::TopState,
+};
+
+// Intrinsic sequences: `Zxz` with angles [a, b, c] is Rz(a) * Rx(b) * Rz(c).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EulerSequence {
+    Xyz,
+    Zyx,
+    Zxz,
+    Zyz,
+    Yxy,
+}
+
+impl EulerSequence {
+    pub const ALL: [EulerSequence; 5] = [
+        EulerSequence::Xyz,
+        EulerSequence::Zyx,
+        EulerSequence::Zxz,
+        EulerSequence::Zyz,
+        EulerSequence::Yxy,
+    ];
+
+    fn axes(&self) -> [usize; 3] {
+        match self {
+            EulerSequence::Xyz => [0, 1, 2],
+            EulerSequence::Zyx => [2, 1, 0],
+            EulerSequence::Zxz => [2, 0, 2],
+            EulerSequence::Zyz => [2, 1, 2],
+            EulerSequence::Yxy => [1, 0, 1],
+        }
+    }
+
+    pub fn rotation<T: Real>(&self, angles: [T; 3]) -> UnitQuaternion<T> {
+        self.axes()
+            .iter()
+            .zip(angles)
+            .map(|(&axis, angle)| {
+                UnitQuaternion::from_axis_angle(
+                    &Unit::new_unchecked(Vector3::ith(axis, T::one())),
+                    angle,
+                )
+            })
+            .fold(UnitQuaternion::identity(), |rotation, step| rotation * step)
+    }
+
+    // Bernardes & Viollet, "Quaternion to Euler angles conversion: A direct, general and
+    // computationally efficient method" (2022). Their sequences are extrinsic, which is the
+    // intrinsic sequence read backwards.
+    pub fn angles<T: Real>(&self, rotation: &UnitQuaternion<T>) -> [T; 3] {
+        let [k, j, i] = self.axes();
+        let proper = i == k;
+        let k = if proper { 3 - i - j } else { k };
+        let [i, j, k] = [i, j, k].map(|axis| axis as i32);
+        let sign = real::<T>(((i - j) * (j - k) * (k - i) / 2) as f64);
+        let [i, j, k] = [i, j, k].map(|axis| axis as usize);
+        let q = [rotation.i, rotation.j, rotation.k];
+        let w = rotation.w;
+
+        let (a, b, c, d) = if proper {
+            (w, q[i], q[j], q[k] * sign)
+        } else {
+            (w - q[j], q[i] + q[k] * sign, q[j] + w, q[k] * sign - q[i])
+        };
+
+        let mut second = real::<T>(2.0) * c.hypot(d).atan2(a.hypot(b));
+        let half_sum = b.atan2(a);
+        let half_difference = d.atan2(c);
+        let first = half_sum - half_difference;
+        let mut third = half_sum + half_difference;
+
+        if !proper {
+            third *= sign;
+            second -= T::frac_pi_2();
+        }
+
+        [third, second, first].map(Self::wrap)
+    }
+
+    fn wrap<T: Real>(angle: T) -> T {
+        if angle > T::pi() {
+            angle - T::two_pi()
+        } else if angle < -T::pi() {
+            angle + T::two_pi()
+        } else {
+            angle
+        }
+    }
+}
+
+impl Display for EulerSequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c] = self.axes().map(|axis| ["X", "Y", "Z"][axis]);
+        write!(f, "{a}-{b}-{c}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Orientation {
+    Euler {
+        sequence: EulerSequence,
+        angles: [f64; 3],
+    },
+    // w, i, j, k; normalised when used.
+    Quaternion([f64; 4]),
+    AxisAngle {
+        axis: [f64; 3],
+        angle: f64,
+    },
+}
+
+impl Orientation {
+    pub const KINDS: [Orientation; 3] = [
+        Orientation::Euler {
+            sequence: EulerSequence::Xyz,
+            angles: [0.0; 3],
+        },
+        Orientation::Quaternion([1.0, 0.0, 0.0, 0.0]),
+        Orientation::AxisAngle {
+            axis: [0.0, 1.0, 0.0],
+            angle: 0.0,
+        },
+    ];
+
+    pub fn same_kind(&self, other: &Orientation) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn rotation<T: Real>(&self) -> UnitQuaternion<T> {
+        match *self {
+            Orientation::Euler { sequence, angles } => sequence.rotation(angles.map(real::<T>)),
+            Orientation::Quaternion([w, i, j, k]) => Unit::try_new(
+                Quaternion::new(real(w), real(i), real(j), real(k)),
+                T::default_epsilon(),
+            )
+            .unwrap_or_else(UnitQuaternion::identity),
+            Orientation::AxisAngle { axis, angle } => {
+                Unit::try_new(Vector3::from(axis.map(real::<T>)), T::default_epsilon())
+                    .map(|axis| UnitQuaternion::from_axis_angle(&axis, real(angle)))
+                    .unwrap_or_else(UnitQuaternion::identity)
+            }
+        }
+    }
+
+    // The same rotation expressed in the representation (and Euler sequence) of `self`.
+    pub fn converted(&self, rotation: &UnitQuaternion<f64>) -> Orientation {
+        match *self {
+            Orientation::Euler { sequence, .. } => Orientation::Euler {
+                sequence,
+                angles: sequence.angles(rotation),
+            },
+            Orientation::Quaternion(_) => {
+                Orientation::Quaternion([rotation.w, rotation.i, rotation.j, rotation.k])
+            }
+            Orientation::AxisAngle { axis, .. } => match rotation.axis_angle() {
+                Some((axis, angle)) => Orientation::AxisAngle {
+                    axis: axis.into_inner().into(),
+                    angle,
+                },
+                None => Orientation::AxisAngle { axis, angle: 0.0 },
+            },
+        }
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Orientation::Euler { .. } => write!(f, "Euler angles"),
+            Orientation::Quaternion(_) => write!(f, "quaternion"),
+            Orientation::AxisAngle { .. } => write!(f, "axis-angle"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VelocityFrame {
+    Body,
+    World,
+}
+
+impl Display for VelocityFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VelocityFrame::Body => write!(f, "body"),
+            VelocityFrame::World => write!(f, "world"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InitialConditions {
+    pub orientation: Orientation,
+    pub angular_velocity: [f64; 3],
+    pub frame: VelocityFrame,
+}
+
+impl InitialConditions {
+    // The classic setup: tilted about x by `deviation` and spinning about the body's y axis.
+    pub fn tilted(deviation: f64, spin: f64) -> Self {
+        Self {
+            orientation: Orientation::Euler {
+                sequence: EulerSequence::Xyz,
+                angles: [deviation, 0.0, 0.0],
+            },
+            angular_velocity: [0.0, spin, 0.0],
+            frame: VelocityFrame::Body,
+        }
+    }
+
+    pub fn world_angular_velocity<T: Real>(&self) -> Vector3<T> {
+        let angular_velocity = Vector3::from(self.angular_velocity.map(real::<T>));
+
+        match self.frame {
+            VelocityFrame::Body => self.orientation.rotation::<T>() * angular_velocity,
+            VelocityFrame::World => angular_velocity,
+        }
+    }
+
+    pub fn state<T: Real>(&self) -> TopState<T> {
+        let q = self.orientation.rotation::<T>();
+
+        TopState::new(
+            q,
+            q.inverse() * self.world_angular_velocity::<T>(),
+            T::zero(),
+        )
+    }
+}
+
+impl Default for InitialConditions {
+    fn default() -> Self {
+        Self::tilted(0.0, 1.0)
+    }
+}
@@ -0,0 +1,107 @@
+use egui::{DragValue, Ui, Widget};
+
+use crate::{
+    body_editor::triple,
+    initial_conditions::{EulerSequence, InitialConditions, Orientation, VelocityFrame},
+};
+
+pub fn initial_conditions_ui(ui: &mut Ui, initial: &mut InitialConditions) -> bool {
+    let mut changed = false;
+    let rotation = initial.orientation.rotation::<f64>();
+    let angle_range = (-std::f64::consts::PI)..=std::f64::consts::PI;
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("orientation")
+            .selected_text(initial.orientation.to_string())
+            .show_ui(ui, |ui| {
+                for kind in Orientation::KINDS {
+                    if ui
+                        .selectable_label(initial.orientation.same_kind(&kind), kind.to_string())
+                        .clicked()
+                        && !initial.orientation.same_kind(&kind)
+                    {
+                        initial.orientation = kind.converted(&rotation);
+                        changed = true;
+                    }
+                }
+            });
+
+        ui.label("orientation");
+    });
+
+    match &mut initial.orientation {
+        Orientation::Euler { sequence, angles } => {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("euler sequence")
+                    .selected_text(sequence.to_string())
+                    .show_ui(ui, |ui| {
+                        for value in EulerSequence::ALL {
+                            if ui
+                                .selectable_label(*sequence == value, value.to_string())
+                                .clicked()
+                                && *sequence != value
+                            {
+                                *sequence = value;
+                                *angles = value.angles(&rotation);
+                                changed = true;
+                            }
+                        }
+                    });
+
+                ui.label("sequence");
+            });
+
+            changed |= triple(ui, angles, angle_range, "angles");
+        }
+        Orientation::Quaternion(components) => {
+            ui.horizontal(|ui| {
+                for value in components.iter_mut() {
+                    changed |= DragValue::new(value)
+                        .clamp_range(-1.0..=1.0)
+                        .speed(0.01)
+                        .ui(ui)
+                        .changed();
+                }
+
+                ui.label("w, i, j, k");
+            });
+        }
+        Orientation::AxisAngle { axis, angle } => {
+            changed |= triple(ui, axis, -1.0..=1.0, "axis");
+
+            ui.horizontal(|ui| {
+                changed |= DragValue::new(angle)
+                    .clamp_range(angle_range)
+                    .speed(0.01)
+                    .ui(ui)
+                    .changed();
+
+                ui.label("angle");
+            });
+        }
+    }
+
+    changed |= triple(
+        ui,
+        &mut initial.angular_velocity,
+        -60.0..=60.0,
+        "angular velocity",
+    );
+
+    ui.horizontal(|ui| {
+        for frame in [VelocityFrame::Body, VelocityFrame::World] {
+            changed |= ui
+                .radio_value(&mut initial.frame, frame, frame.to_string())
+                .changed();
+        }
+
+        ui.label("angular velocity frame");
+    });
+
+    ui.label(format!(
+        "|ω|: {:.3}",
+        initial.world_angular_velocity::<f64>().norm()
+    ));
+
+    changed
+}
@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+use derive_new::new;
+use nalgebra::{Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    crouch_grossman_integrator::CrouchGrossmanIntegrator,
+    dormand_prince_integrator::DormandPrinceIntegrator, euler_integrator::EulerIntegrator,
+    real::Real, runge_kutta_integrator::RungeKuttaIntegrator, simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+#[derive(Debug, Clone, Copy, new)]
+pub struct ErrorEstimate<T: Real> {
+    pub q: Vector4<T>,
+    pub w: Vector3<T>,
+}
+
+#[derive(Debug, Clone, Copy, new)]
+pub struct StepResult<T: Real> {
+    pub state: TopState<T>,
+    pub quaternion_norm: T,
+    pub error: Option<ErrorEstimate<T>>,
+}
+
+pub trait Integrator<T: Real>: Send {
+    fn step(&mut self, dynamics: &TopDynamics<T>, state: &TopState<T>, h: T) -> StepResult<T>;
+
+    fn embedded_order(&self) -> Option<i32> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IntegratorKind {
+    Euler,
+    RungeKutta4,
+    DormandPrince,
+    CrouchGrossman,
+}
+
+impl IntegratorKind {
+    pub const ALL: [IntegratorKind; 4] = [
+        IntegratorKind::Euler,
+        IntegratorKind::RungeKutta4,
+        IntegratorKind::DormandPrince,
+        IntegratorKind::CrouchGrossman,
+    ];
+
+    pub fn create<T: Real>(self) -> Box<dyn Integrator<T>> {
+        match self {
+            IntegratorKind::Euler => Box::new(EulerIntegrator),
+            IntegratorKind::RungeKutta4 => Box::new(RungeKuttaIntegrator),
+            IntegratorKind::DormandPrince => Box::new(DormandPrinceIntegrator),
+            IntegratorKind::CrouchGrossman => Box::new(CrouchGrossmanIntegrator),
+        }
+    }
+
+    pub fn is_embedded(self) -> bool {
+        self.create::<f32>().embedded_order().is_some()
+    }
+}
+
+impl Display for IntegratorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            IntegratorKind::Euler => "explicit Euler",
+            IntegratorKind::RungeKutta4 => "RK4",
+            IntegratorKind::DormandPrince => "Dormand-Prince RK45",
+            IntegratorKind::CrouchGrossman => "Crouch-Grossman (SO(3))",
+        })
+    }
+}
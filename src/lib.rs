@@ -0,0 +1,134 @@
+// Physics of a heavy spinning top and the tools built on it. With the `render` feature come
+// the glium drawers, with `viewer` the egui viewer itself.
+//
+// The modules are private and everything meant for use outside the crate is re-exported
+// below, so the source can be reorganised without breaking dependants.
+
+mod analytic_solution;
+mod body_definition;
+mod camera;
+mod config;
+mod crouch_grossman_integrator;
+mod cube;
+mod diagnostics;
+mod dormand_prince_integrator;
+mod elliptic;
+mod euler_integrator;
+mod export;
+mod headless;
+mod history;
+mod imported_mesh;
+mod initial_conditions;
+mod integrator;
+mod mesh;
+mod pivot;
+mod precession_analyser;
+mod real;
+mod recording;
+mod rigid_body;
+mod runge_kutta_integrator;
+mod shape;
+mod simulation;
+mod simulation_clock;
+mod simulation_parameters;
+mod simulation_state;
+mod simulation_thread;
+mod stability;
+mod stability_map;
+mod step_controller;
+mod sweep;
+mod top_dynamics;
+mod vertex;
+
+// The allowed lints are in code carried over unchanged from before the simulator was split
+// out, left as written rather than restyled.
+#[cfg(feature = "render")]
+mod angular_velocity_drawer;
+#[cfg(feature = "render")]
+#[allow(clippy::needless_borrow)]
+mod cuber_drawer;
+#[cfg(feature = "render")]
+mod diagonal_drawer;
+#[cfg(feature = "render")]
+mod gravity_vector_drawer;
+#[cfg(feature = "render")]
+#[allow(clippy::needless_borrow)]
+mod infinite_grid_drawer;
+#[cfg(feature = "render")]
+mod mesh_drawer;
+#[cfg(feature = "render")]
+#[allow(clippy::map_clone)]
+mod trajectory;
+#[cfg(feature = "render")]
+#[allow(clippy::needless_borrow, clippy::needless_borrows_for_generic_args)]
+mod trajectory_drawer;
+
+#[cfg(feature = "viewer")]
+mod body_editor;
+#[cfg(feature = "viewer")]
+mod export_window;
+#[cfg(feature = "viewer")]
+mod initial_conditions_editor;
+#[cfg(feature = "viewer")]
+mod shape_editor;
+#[cfg(feature = "viewer")]
+mod stability_map_window;
+#[cfg(feature = "viewer")]
+#[allow(clippy::collapsible_match, clippy::single_match)]
+pub mod viewer;
+
+// Bodies and their mass properties.
+pub use body_definition::{BodyDefinition, PrincipalInertia};
+pub use cube::Cube;
+pub use imported_mesh::{ImportedMesh, MeshError};
+pub use mesh::Mesh;
+pub use pivot::Pivot;
+pub use rigid_body::RigidBody;
+pub use shape::{MassProperties, Shape};
+pub use vertex::Vertex;
+
+// Simulation.
+pub use crouch_grossman_integrator::CrouchGrossmanIntegrator;
+pub use dormand_prince_integrator::DormandPrinceIntegrator;
+pub use euler_integrator::EulerIntegrator;
+pub use initial_conditions::{EulerSequence, InitialConditions, Orientation, VelocityFrame};
+pub use integrator::{ErrorEstimate, Integrator, IntegratorKind, StepResult};
+pub use real::{real, Precision, Real};
+pub use runge_kutta_integrator::RungeKuttaIntegrator;
+pub use simulation::{Simulator, TopState};
+pub use simulation_parameters::{SimulationParameters, StepControllerSettings};
+pub use step_controller::AdaptiveStepController;
+pub use top_dynamics::TopDynamics;
+
+// Running simulations: in a background thread paced by a clock, or headless.
+pub use headless::{run_headless, Divergence, HeadlessSettings, RunSummary};
+pub use simulation_clock::{ClockMode, ClockReadout, SimulationClock};
+pub use simulation_state::SimulationState;
+pub use simulation_thread::{spawn_simulation, DiagnosticsReadout, SharedSimulation};
+pub use sweep::{Sweep, SweepPoint, SweepResult};
+
+// Analysis.
+pub use analytic_solution::AnalyticSolution;
+pub use diagnostics::{Diagnostics, Drift};
+pub use precession_analyser::{PrecessionAnalyser, PrecessionReadout};
+pub use stability::StabilityAnalysis;
+pub use stability_map::{critical_spin, fall_time, Cell, StabilityMap, StabilityMapSettings};
+
+// Files: configurations, recordings and exported data.
+pub use camera::Camera;
+pub use config::{Config, ConfigError, DrawSettings};
+pub use export::{export, Column, ExportFormat, ExportSettings};
+pub use history::{History, HISTORY_CAPACITY};
+pub use recording::{Recording, RecordingError};
+
+// The drawers take the display they draw on and the view and perspective matrices, so they
+// can be used in any glium application.
+#[cfg(feature = "render")]
+pub mod render {
+    pub use crate::{
+        angular_velocity_drawer::AngularVelocityDrawer, cuber_drawer::CubeDrawer,
+        diagonal_drawer::DiagonalDrawer, gravity_vector_drawer::GravityVectorDrawer,
+        infinite_grid_drawer::InfiniteGridDrawer, mesh_drawer::MeshDrawer, trajectory::Trajectory,
+        trajectory_drawer::TrajectoryDrawer,
+    };
+}
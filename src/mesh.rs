@@ -0,0 +1,99 @@
+use std::f32::consts::PI;
+
+use derive_getters::Getters;
+use derive_new::new;
+
+use crate::vertex::Vertex;
+
+const SEGMENTS: usize = 48;
+
+#[derive(Debug, Clone, Getters, new)]
+pub struct Mesh {
+    vertices: Vec<Vertex>,
+    indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn flip(&mut self) {
+        for triangle in self.indices.chunks_exact_mut(3) {
+            triangle.swap(1, 2);
+        }
+    }
+
+    pub fn cylinder(radius: f32, inner_radius: f32, height: f32) -> Self {
+        if inner_radius > 0.0 {
+            Self::revolution(
+                &[
+                    (inner_radius, 0.0),
+                    (radius, 0.0),
+                    (radius, height),
+                    (inner_radius, height),
+                    (inner_radius, 0.0),
+                ],
+                1.0,
+            )
+        } else {
+            Self::revolution(
+                &[(0.0, 0.0), (radius, 0.0), (radius, height), (0.0, height)],
+                1.0,
+            )
+        }
+    }
+
+    pub fn cone(radius: f32, height: f32) -> Self {
+        Self::revolution(&[(0.0, 0.0), (radius, height), (0.0, height)], 1.0)
+    }
+
+    pub fn ellipsoid(x: f32, y: f32, z: f32) -> Self {
+        let profile = (0..=SEGMENTS / 2)
+            .map(|i| {
+                let angle = PI * i as f32 / (SEGMENTS / 2) as f32;
+                (x * angle.sin(), y - y * angle.cos())
+            })
+            .collect::<Vec<_>>();
+
+        Self::revolution(&profile, z / x)
+    }
+
+    pub fn disc(radius: f32, thickness: f32, stem: f32) -> Self {
+        let bottom = stem - thickness / 2.0;
+        let top = stem + thickness / 2.0;
+
+        Self::revolution(
+            &[(0.0, bottom), (radius, bottom), (radius, top), (0.0, top)],
+            1.0,
+        )
+    }
+
+    // Sweeps a (radius, height) profile around the y axis. The profile has to run
+    // counterclockwise around the solid's cross-section so that faces point outwards.
+    fn revolution(profile: &[(f32, f32)], z_scale: f32) -> Self {
+        let vertices = profile
+            .iter()
+            .flat_map(|(r, y)| {
+                (0..SEGMENTS).map(move |j| {
+                    let angle = 2.0 * PI * j as f32 / SEGMENTS as f32;
+                    Vertex::new([r * angle.cos(), *y, r * angle.sin() * z_scale])
+                })
+            })
+            .collect();
+
+        let index = |i: usize, j: usize| (i * SEGMENTS + j % SEGMENTS) as u32;
+        let indices = (0..profile.len() - 1)
+            .flat_map(|i| {
+                (0..SEGMENTS).flat_map(move |j| {
+                    [
+                        index(i, j),
+                        index(i + 1, j),
+                        index(i, j + 1),
+                        index(i, j + 1),
+                        index(i + 1, j),
+                        index(i + 1, j + 1),
+                    ]
+                })
+            })
+            .collect();
+
+        Self { vertices, indices }
+    }
+}
@@ -0,0 +1,78 @@
+use glium::glutin::surface::WindowSurface;
+use glium::{uniform, Display, DrawParameters, IndexBuffer, Program, Surface, VertexBuffer};
+use nalgebra::Matrix4;
+
+use crate::mesh::Mesh;
+use crate::rigid_body::RigidBody;
+use crate::vertex::Vertex;
+
+pub struct MeshDrawer {
+    program: Program,
+    vertex_buffer: VertexBuffer<Vertex>,
+    index_buffer: IndexBuffer<u32>,
+}
+
+impl MeshDrawer {
+    pub fn new(display: &Display<WindowSurface>, mesh: &Mesh) -> Self {
+        let vertex_shader_src = r#"
+            #version 410 core
+
+            in vec3 position;
+
+            uniform mat4 perspective;
+            uniform mat4 view;
+            uniform mat4 model;
+
+            void main() {
+                gl_Position = perspective * view * model * vec4(position, 1.0);
+            }
+        "#;
+
+        let fragment_shader_src = r#"
+            #version 410 core
+
+            out vec4 color;
+            
+            void main() {
+                color = vec4(1, 1, 1, 0.5);
+            }
+        "#;
+
+        let program =
+            Program::from_source(display, vertex_shader_src, fragment_shader_src, None).unwrap();
+
+        Self {
+            program,
+            vertex_buffer: VertexBuffer::new(display, mesh.vertices()).unwrap(),
+            index_buffer: IndexBuffer::new(
+                display,
+                glium::index::PrimitiveType::TrianglesList,
+                mesh.indices(),
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn draw(
+        &self,
+        target: &mut glium::Frame,
+        perspective: &Matrix4<f32>,
+        view: &Matrix4<f32>,
+        body: &RigidBody<f32>,
+        drawing_parameters: &DrawParameters,
+    ) {
+        target
+            .draw(
+                &self.vertex_buffer,
+                &self.index_buffer,
+                &self.program,
+                &uniform! {
+                    perspective: perspective.data.0,
+                    view: view.data.0,
+                    model: body.get_model_matrix().data.0,
+                },
+                drawing_parameters,
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::Display;
+
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    real::{real, Real},
+    shape::Shape,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Pivot {
+    #[default]
+    Natural,
+    Vertex(usize),
+    EdgeMidpoint(usize),
+    FaceCenter(usize),
+    Custom([f64; 3]),
+}
+
+impl Pivot {
+    pub const KINDS: [Pivot; 5] = [
+        Pivot::Natural,
+        Pivot::Vertex(0),
+        Pivot::EdgeMidpoint(0),
+        Pivot::FaceCenter(0),
+        Pivot::Custom([0.0; 3]),
+    ];
+
+    pub fn same_kind(&self, other: &Pivot) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            Pivot::Vertex(_) => 8,
+            Pivot::EdgeMidpoint(_) => 12,
+            Pivot::FaceCenter(_) => 6,
+            Pivot::Natural | Pivot::Custom(_) => 1,
+        }
+    }
+
+    // Vertices, edges and faces are only defined for box shapes, and only up to `count`; the
+    // point is in the shape frame.
+    pub fn point<T: Real>(&self, shape: &Shape) -> Option<Vector3<T>> {
+        if let Pivot::Vertex(i) | Pivot::EdgeMidpoint(i) | Pivot::FaceCenter(i) = *self {
+            if i >= self.count() {
+                return None;
+            }
+        }
+
+        let fractions = match *self {
+            Pivot::Natural => return Some(Vector3::zeros()),
+            Pivot::Custom(point) => return Some(Vector3::from(point.map(real::<T>))),
+            Pivot::Vertex(i) => {
+                Vector3::new(i & 1, (i >> 1) & 1, (i >> 2) & 1).map(|bit| bit as f64)
+            }
+            Pivot::EdgeMidpoint(i) => {
+                let mut fractions = Vector3::repeat(0.0);
+                fractions[(i / 4 + 1) % 3] = (i & 1) as f64;
+                fractions[(i / 4 + 2) % 3] = ((i >> 1) & 1) as f64;
+                fractions[i / 4] = 0.5;
+                fractions
+            }
+            Pivot::FaceCenter(i) => {
+                let mut fractions = Vector3::repeat(0.5);
+                fractions[i / 2] = (i % 2) as f64;
+                fractions
+            }
+        };
+
+        shape
+            .box_extent()
+            .map(|extent| extent.component_mul(&fractions).map(real::<T>))
+    }
+}
+
+impl Display for Pivot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pivot::Natural => write!(f, "natural"),
+            Pivot::Vertex(_) => write!(f, "vertex"),
+            Pivot::EdgeMidpoint(_) => write!(f, "edge midpoint"),
+            Pivot::FaceCenter(_) => write!(f, "face centre"),
+            Pivot::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
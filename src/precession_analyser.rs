@@ -0,0 +1,138 @@
+use std::f64::consts::{PI, TAU};
+
+use crate::{initial_conditions::EulerSequence, rigid_body::RigidBody, simulation::TopState};
+
+const EXTREMA: usize = 8;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrecessionReadout {
+    pub precession: f64,
+    pub nutation: f64,
+    pub spin: f64,
+    pub precession_period: Option<f64>,
+    pub precession_rate: Option<f64>,
+    pub gyroscopic_rate: Option<f64>,
+    pub nutation_amplitude: Option<f64>,
+    pub nutation_frequency: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    t: f64,
+    precession: f64,
+    nutation: f64,
+}
+
+// Splits the orientation into Y-X-Y angles of the body's y axis about the vertical: precession
+// φ, nutation θ and spin ψ. φ is unwrapped so the period can be read off its 2π crossings,
+// and the nutation is measured between successive extrema of θ.
+pub struct PrecessionAnalyser {
+    weight_arm: f64,
+    axial_moment: f64,
+    start: Option<Sample>,
+    samples: Vec<Sample>,
+    crossings: Vec<f64>,
+    maxima: Vec<Sample>,
+    minima: Vec<Sample>,
+    readout: PrecessionReadout,
+}
+
+impl PrecessionAnalyser {
+    pub fn new(body: &RigidBody<f64>) -> Self {
+        Self {
+            weight_arm: body.mass() * 9.81 * body.center_of_mass().y,
+            axial_moment: body.moment_of_interia()[(1, 1)],
+            start: None,
+            samples: Vec::with_capacity(3),
+            crossings: Vec::new(),
+            maxima: Vec::new(),
+            minima: Vec::new(),
+            readout: PrecessionReadout::default(),
+        }
+    }
+
+    pub fn readout(&self) -> PrecessionReadout {
+        self.readout
+    }
+
+    pub fn record(&mut self, state: &TopState<f64>, gravity: bool) {
+        let [precession, nutation, spin] = EulerSequence::Yxy.angles(&state.q);
+        let spin_rate = state.w.y;
+
+        self.readout.precession = precession;
+        self.readout.nutation = nutation;
+        self.readout.spin = spin;
+        self.readout.gyroscopic_rate = (gravity && spin_rate.abs() > 1e-9)
+            .then(|| self.weight_arm / (self.axial_moment * spin_rate));
+
+        // Precession is undefined with the axis (nearly) vertical.
+        if nutation.sin() < 1e-3 {
+            return;
+        }
+
+        let sample = match self.samples.last() {
+            Some(last) => Sample {
+                t: state.t,
+                precession: last.precession + wrap(precession - last.precession),
+                nutation,
+            },
+            None => Sample {
+                t: state.t,
+                precession,
+                nutation,
+            },
+        };
+        let start = *self.start.get_or_insert(sample);
+
+        if let Some(last) = self.samples.last() {
+            let (before, after) = (
+                (last.precession / TAU).floor(),
+                (sample.precession / TAU).floor(),
+            );
+            if before != after {
+                let boundary = before.max(after) * TAU;
+                let fraction = (boundary - last.precession) / (sample.precession - last.precession);
+                push_bounded(&mut self.crossings, last.t + fraction * (sample.t - last.t));
+            }
+        }
+
+        if sample.t > start.t {
+            self.readout.precession_rate =
+                Some((sample.precession - start.precession) / (sample.t - start.t));
+        }
+        if let [.., previous, last] = self.crossings[..] {
+            self.readout.precession_period = Some(last - previous);
+        }
+
+        if self.samples.len() == 3 {
+            self.samples.remove(0);
+        }
+        self.samples.push(sample);
+
+        if let [first, middle, last] = self.samples[..] {
+            if middle.nutation > first.nutation && middle.nutation >= last.nutation {
+                push_bounded(&mut self.maxima, middle);
+            } else if middle.nutation < first.nutation && middle.nutation <= last.nutation {
+                push_bounded(&mut self.minima, middle);
+            }
+        }
+
+        if let (Some(maximum), Some(minimum)) = (self.maxima.last(), self.minima.last()) {
+            self.readout.nutation_amplitude = Some((maximum.nutation - minimum.nutation) / 2.0);
+        }
+        if let [.., previous, last] = self.maxima[..] {
+            self.readout.nutation_frequency = Some(1.0 / (last.t - previous.t));
+        }
+    }
+}
+
+fn wrap(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+fn push_bounded<T>(values: &mut Vec<T>, value: T) {
+    if values.len() == EXTREMA {
+        values.remove(0);
+    }
+    values.push(value);
+}
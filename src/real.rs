@@ -0,0 +1,51 @@
+use std::fmt::Display;
+
+use nalgebra::{convert, convert_unchecked, Quaternion, RealField, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+pub trait Real: RealField + Copy {
+    fn to_f64(self) -> f64 {
+        convert_unchecked(self)
+    }
+
+    fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    fn cast<U: Real>(self) -> U {
+        real(self.to_f64())
+    }
+}
+
+impl<T: RealField + Copy> Real for T {}
+
+pub fn real<T: Real>(value: f64) -> T {
+    convert(value)
+}
+
+pub fn vector_to_f32<T: Real>(v: &Vector3<T>) -> Vector3<f32> {
+    v.map(Real::to_f32)
+}
+
+pub fn quaternion_to_f32<T: Real>(q: &UnitQuaternion<T>) -> UnitQuaternion<f32> {
+    UnitQuaternion::new_unchecked(Quaternion::from(q.coords.map(Real::to_f32)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Precision {
+    Single,
+    Double,
+}
+
+impl Precision {
+    pub const ALL: [Precision; 2] = [Precision::Single, Precision::Double];
+}
+
+impl Display for Precision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Precision::Single => "f32",
+            Precision::Double => "f64",
+        })
+    }
+}
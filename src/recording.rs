@@ -0,0 +1,178 @@
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::ConfigError,
+    history::{History, HISTORY_CAPACITY},
+    simulation::TopState,
+    simulation_parameters::SimulationParameters,
+};
+
+// Layout, all little-endian:
+//   magic, format version (u32), header length (u64), JSON header,
+//   sample count (u64), samples of t, q (w, i, j, k), body ω (x, y, z) and |q| − 1 as f64.
+// New header fields must come with serde defaults so older recordings keep loading.
+// Version 1 has no |q| − 1 in its samples and only the path of imported meshes, where
+// version 2 embeds their geometry.
+const MAGIC: &[u8; 8] = b"SPINTOP\0";
+pub const FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(io::Error),
+    NotARecording,
+    UnsupportedVersion(u32),
+    Header(serde_json::Error),
+    Parameters(ConfigError),
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(f, "{}", error),
+            RecordingError::NotARecording => write!(f, "not a spinning top recording"),
+            RecordingError::UnsupportedVersion(version) => write!(
+                f,
+                "recording format version {} is newer than the supported {}",
+                version, FORMAT_VERSION
+            ),
+            RecordingError::Header(error) => write!(f, "invalid header: {}", error),
+            RecordingError::Parameters(error) => write!(f, "invalid run parameters: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for RecordingError {}
+
+impl From<io::Error> for RecordingError {
+    fn from(error: io::Error) -> Self {
+        RecordingError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for RecordingError {
+    fn from(error: serde_json::Error) -> Self {
+        RecordingError::Header(error)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    parameters: SimulationParameters,
+    gravity_changes: Vec<(f64, bool)>,
+}
+
+// A run's parameters and its history, replayed without simulating.
+pub struct Recording {
+    pub parameters: SimulationParameters,
+    pub history: History,
+}
+
+impl Recording {
+    pub fn save(
+        path: impl AsRef<Path>,
+        parameters: &SimulationParameters,
+        history: &History,
+    ) -> Result<(), RecordingError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let header = serde_json::to_vec(&Header {
+            parameters: parameters.clone(),
+            gravity_changes: history.gravity_changes().to_vec(),
+        })?;
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&(header.len() as u64).to_le_bytes())?;
+        writer.write_all(&header)?;
+        writer.write_all(&(history.samples().len() as u64).to_le_bytes())?;
+
+        for (sample, norm_error) in history
+            .samples()
+            .iter()
+            .zip(history.quaternion_norm_errors())
+        {
+            let q = sample.q.quaternion();
+            for value in [
+                sample.t,
+                q.w,
+                q.i,
+                q.j,
+                q.k,
+                sample.w.x,
+                sample.w.y,
+                sample.w.z,
+                *norm_error,
+            ] {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+
+        Ok(writer.flush()?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(RecordingError::NotARecording);
+        }
+
+        let version = u32::from_le_bytes(read_array(&mut reader)?);
+        if version > FORMAT_VERSION {
+            return Err(RecordingError::UnsupportedVersion(version));
+        }
+
+        let length = u64::from_le_bytes(read_array(&mut reader)?);
+        let mut header = Vec::new();
+        reader.by_ref().take(length).read_to_end(&mut header)?;
+        let header: Header = serde_json::from_slice(&header)?;
+        // Replays and branches start a simulator from these, so they are held to what a
+        // config has to satisfy.
+        header
+            .parameters
+            .validate()
+            .map_err(RecordingError::Parameters)?;
+
+        // The count is not trusted for the allocation, a truncated file fails on reading.
+        let count = u64::from_le_bytes(read_array(&mut reader)?) as usize;
+        let mut samples = Vec::with_capacity(count.min(HISTORY_CAPACITY));
+        let mut norm_errors = Vec::with_capacity(count.min(HISTORY_CAPACITY));
+        for _ in 0..count {
+            let bytes = read_array::<64>(&mut reader)?;
+            let [t, w, i, j, k, x, y, z] = std::array::from_fn(|index| {
+                f64::from_le_bytes(bytes[index * 8..(index + 1) * 8].try_into().unwrap())
+            });
+            samples.push(TopState::new(
+                UnitQuaternion::new_unchecked(Quaternion::new(w, i, j, k)),
+                Vector3::new(x, y, z),
+                t,
+            ));
+            norm_errors.push(if version >= 2 {
+                f64::from_le_bytes(read_array(&mut reader)?)
+            } else {
+                f64::NAN
+            });
+        }
+
+        Ok(Self {
+            parameters: header.parameters,
+            history: History::from_parts(samples, norm_errors, header.gravity_changes),
+        })
+    }
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
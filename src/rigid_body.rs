@@ -0,0 +1,160 @@
+use derive_getters::Getters;
+use derive_setters::Setters;
+use nalgebra::{Isometry3, Matrix3, Matrix4, Point3, Translation3, UnitQuaternion, Vector3};
+
+use crate::{
+    body_definition::PrincipalInertia,
+    cube::Cube,
+    pivot::Pivot,
+    real::{real, Real},
+    shape::Shape,
+};
+
+#[derive(Debug, Clone, Getters, Setters)]
+#[setters(generate = false, prefix = "set_", borrow_self)]
+pub struct RigidBody<T: Real> {
+    shape: Shape,
+    #[getter(copy)]
+    mass: T,
+    #[getter(copy)]
+    center_of_mass: Vector3<T>,
+    #[getter(copy)]
+    moment_of_interia: Matrix3<T>,
+    #[getter(copy)]
+    tip: Vector3<T>,
+    #[getter(copy)]
+    shape_transform: Isometry3<T>,
+    #[getter(copy)]
+    #[setters(generate)]
+    rotation: UnitQuaternion<T>,
+}
+
+impl<T: Real> RigidBody<T> {
+    pub fn from_shape(shape: Shape, density: T, pivot: Pivot, unbalance: Vector3<T>) -> Self {
+        let pivot = pivot.point::<T>(&shape).map_or(Pivot::Natural, |_| pivot);
+
+        if let (Shape::Cube { size }, Pivot::Natural) = (&shape, pivot) {
+            if unbalance == Vector3::zeros() {
+                return Self::from(&Cube::standing_on_vertex(nalgebra::convert(*size), density));
+            }
+        }
+
+        let properties = shape.mass_properties(density);
+        let pivot_point = pivot.point(&shape).unwrap_or_else(Vector3::zeros);
+        let axis = properties.center - pivot_point;
+        // Away from the natural pivot the tip is the point opposite the pivot through the
+        // centre of mass, e.g. the opposite vertex, edge or face of a box.
+        let tip = match pivot {
+            Pivot::Natural => shape.tip(),
+            _ => properties.center * real::<T>(2.0) - pivot_point,
+        } - pivot_point;
+        let base_rotation = UnitQuaternion::rotation_between(&axis, &Vector3::y())
+            .filter(|_| axis.norm() > T::default_epsilon())
+            .unwrap_or_else(UnitQuaternion::identity);
+        let rotation_matrix = base_rotation.to_rotation_matrix();
+        // The unbalance moves the centre of mass off the geometric axis and leaves the central
+        // inertia as it is.
+        let center_of_mass = rotation_matrix * (axis + unbalance);
+
+        Self {
+            shape,
+            mass: properties.mass,
+            center_of_mass,
+            moment_of_interia: rotation_matrix.matrix()
+                * properties.inertia
+                * rotation_matrix.matrix().transpose()
+                + Self::parallel_axis_shift(properties.mass, &center_of_mass),
+            tip: rotation_matrix * tip,
+            shape_transform: Isometry3::from_parts(Translation3::identity(), base_rotation)
+                * Translation3::from(-pivot_point),
+            rotation: UnitQuaternion::identity(),
+        }
+    }
+
+    // The moments are principal moments about the pivot, not about the centre of mass, which is
+    // how the classical Euler, Lagrange and Kovalevskaya tops are usually stated.
+    pub fn from_principal_moments(inertia: &PrincipalInertia) -> Self {
+        let mass = real::<T>(inertia.mass);
+        let [x, y, z] = inertia.moments.map(real::<T>);
+        let [roll, pitch, yaw] = inertia.axes.map(real::<T>);
+        let axes = UnitQuaternion::from_euler_angles(roll, pitch, yaw);
+        let axes_matrix = axes.to_rotation_matrix();
+        let center_of_mass = Vector3::from(inertia.center_of_mass.map(real::<T>));
+        let moment_of_interia = axes_matrix.matrix()
+            * Matrix3::from_diagonal(&Vector3::new(x, y, z))
+            * axes_matrix.matrix().transpose();
+
+        // Drawn as the uniform ellipsoid with the same central principal moments, placed at the
+        // centre of mass along the principal axes.
+        let central = axes_matrix.matrix().transpose()
+            * (moment_of_interia - Self::parallel_axis_shift(mass, &center_of_mass))
+            * axes_matrix.matrix();
+        let semi_axis = |a: usize, b: usize, c: usize| {
+            (real::<T>(2.5) / mass * (central[(b, b)] + central[(c, c)] - central[(a, a)]))
+                .max(real(1e-4))
+                .sqrt()
+                .to_f64()
+        };
+        let shape = Shape::Ellipsoid {
+            x: semi_axis(0, 1, 2),
+            y: semi_axis(1, 0, 2),
+            z: semi_axis(2, 0, 1),
+        };
+        let shape_center = Vector3::new(T::zero(), real(semi_axis(1, 0, 2)), T::zero());
+        let shape_transform = Isometry3::from_parts(Translation3::from(center_of_mass), axes)
+            * Translation3::from(-shape_center);
+
+        Self {
+            tip: shape_transform
+                .transform_point(&Point3::from(shape.tip::<T>()))
+                .coords,
+            shape,
+            mass,
+            center_of_mass,
+            moment_of_interia,
+            shape_transform,
+            rotation: UnitQuaternion::identity(),
+        }
+    }
+
+    fn parallel_axis_shift(mass: T, offset: &Vector3<T>) -> Matrix3<T> {
+        (Matrix3::identity() * offset.norm_squared() - offset * offset.transpose()) * mass
+    }
+}
+
+impl RigidBody<f32> {
+    pub fn get_model_matrix(&self) -> Matrix4<f32> {
+        self.rotation.to_rotation_matrix().to_homogeneous()
+            * self.shape_transform.to_homogeneous()
+            * Matrix4::new_nonuniform_scaling(&self.shape.model_scaling())
+    }
+
+    pub fn get_diagonal_model_matrix(&self) -> Matrix4<f32> {
+        let direction = UnitQuaternion::rotation_between(&Vector3::y(), &self.tip)
+            .unwrap_or_else(UnitQuaternion::identity);
+
+        self.rotation.to_rotation_matrix().to_homogeneous()
+            * direction.to_rotation_matrix().to_homogeneous()
+            * Matrix4::new_scaling(self.tip.norm())
+    }
+
+    pub fn get_gravity_vector_model_matrix(&self) -> Matrix4<f32> {
+        Matrix4::new_translation(&(self.rotation.to_rotation_matrix() * self.center_of_mass))
+    }
+}
+
+impl<T: Real> From<&Cube<T>> for RigidBody<T> {
+    fn from(cube: &Cube<T>) -> Self {
+        Self {
+            shape: Shape::Cube {
+                size: cube.size().to_f64(),
+            },
+            mass: cube.get_weight(),
+            center_of_mass: cube.get_center(),
+            moment_of_interia: cube.get_moment_of_interia(),
+            tip: cube.get_top(),
+            shape_transform: Isometry3::from_parts(Translation3::identity(), cube.base_rotation()),
+            rotation: cube.rotation(),
+        }
+    }
+}
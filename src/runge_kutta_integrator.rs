@@ -0,0 +1,50 @@
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+
+use crate::{
+    integrator::{Integrator, StepResult},
+    real::{real, Real},
+    simulation::TopState,
+    top_dynamics::TopDynamics,
+};
+
+pub struct RungeKuttaIntegrator;
+
+impl RungeKuttaIntegrator {
+    fn derivative<T: Real>(
+        dynamics: &TopDynamics<T>,
+        q: Quaternion<T>,
+        w: Vector3<T>,
+    ) -> (Quaternion<T>, Vector3<T>) {
+        (
+            TopDynamics::quaternion_derivative(&q, &w),
+            dynamics.angular_acceleration(&UnitQuaternion::from_quaternion(q), &w),
+        )
+    }
+}
+
+impl<T: Real> Integrator<T> for RungeKuttaIntegrator {
+    fn step(&mut self, dynamics: &TopDynamics<T>, state: &TopState<T>, h: T) -> StepResult<T> {
+        let w = state.w;
+        let q = *state.q.quaternion();
+        let half = h / real(2.0);
+
+        let (k1_q, k1_w) = Self::derivative(dynamics, q, w);
+        let (k2_q, k2_w) = Self::derivative(dynamics, q + k1_q * half, w + k1_w * half);
+        let (k3_q, k3_w) = Self::derivative(dynamics, q + k2_q * half, w + k2_w * half);
+        let (k4_q, k4_w) = Self::derivative(dynamics, q + k3_q * h, w + k3_w * h);
+
+        let sixth = h / real(6.0);
+        let two = real::<T>(2.0);
+        let new_q = q + (k1_q + k2_q * two + k3_q * two + k4_q) * sixth;
+
+        StepResult::new(
+            TopState::new(
+                UnitQuaternion::from_quaternion(new_q),
+                w + (k1_w + k2_w * two + k3_w * two + k4_w) * sixth,
+                state.t + h,
+            ),
+            new_q.norm(),
+            None,
+        )
+    }
+}
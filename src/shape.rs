@@ -0,0 +1,278 @@
+use std::{fmt::Display, sync::Arc};
+
+use derive_new::new;
+use nalgebra::{Matrix3, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    imported_mesh::ImportedMesh,
+    mesh::Mesh,
+    real::{real, Real},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Shape {
+    Cube {
+        size: f64,
+    },
+    Cuboid {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Cylinder {
+        radius: f64,
+        inner_radius: f64,
+        height: f64,
+    },
+    Cone {
+        radius: f64,
+        height: f64,
+    },
+    Ellipsoid {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Disc {
+        radius: f64,
+        thickness: f64,
+        stem: f64,
+    },
+    Mesh(Arc<ImportedMesh>),
+}
+
+#[derive(Debug, Clone, Copy, new)]
+pub struct MassProperties<T: Real> {
+    pub mass: T,
+    pub center: Vector3<T>,
+    pub inertia: Matrix3<T>,
+}
+
+impl Shape {
+    pub const DEFAULTS: [Shape; 6] = [
+        Shape::Cube { size: 1.0 },
+        Shape::Cuboid {
+            x: 1.0,
+            y: 0.5,
+            z: 0.25,
+        },
+        Shape::Cylinder {
+            radius: 0.5,
+            inner_radius: 0.0,
+            height: 1.0,
+        },
+        Shape::Cone {
+            radius: 0.5,
+            height: 1.0,
+        },
+        Shape::Ellipsoid {
+            x: 0.5,
+            y: 0.25,
+            z: 0.5,
+        },
+        Shape::Disc {
+            radius: 1.0,
+            thickness: 0.05,
+            stem: 0.5,
+        },
+    ];
+
+    // Whether the dimensions describe a solid, so its inertia can be inverted. Imported meshes
+    // are checked when they are loaded.
+    pub fn is_physical(&self) -> bool {
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+
+        match *self {
+            Shape::Cube { size } => positive(size),
+            Shape::Cuboid { x, y, z } | Shape::Ellipsoid { x, y, z } => {
+                positive(x) && positive(y) && positive(z)
+            }
+            Shape::Cylinder {
+                radius,
+                inner_radius,
+                height,
+            } => positive(radius) && positive(height) && (0.0..radius).contains(&inner_radius),
+            Shape::Cone { radius, height } => positive(radius) && positive(height),
+            Shape::Disc {
+                radius,
+                thickness,
+                stem,
+            } => positive(radius) && positive(thickness) && stem.is_finite() && stem >= 0.0,
+            Shape::Mesh(_) => true,
+        }
+    }
+
+    pub fn same_kind(&self, other: &Shape) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    // Shapes are described in their own frame with the natural pivot at the origin:
+    // a cube vertex, the centre of a cylinder's base, a cone's apex, the bottom of an
+    // ellipsoid and the lower end of a disc's stem.
+    pub fn mass_properties<T: Real>(&self, density: T) -> MassProperties<T> {
+        let pi = T::pi();
+        let half = real::<T>(0.5);
+        let twelfth = real::<T>(1.0 / 12.0);
+
+        match *self {
+            Shape::Cube { size } => Shape::Cuboid {
+                x: size,
+                y: size,
+                z: size,
+            }
+            .mass_properties(density),
+            Shape::Cuboid { x, y, z } => {
+                let (x, y, z) = (real::<T>(x), real::<T>(y), real::<T>(z));
+                let mass = density * x * y * z;
+
+                MassProperties::new(
+                    mass,
+                    Vector3::new(x, y, z) * half,
+                    Matrix3::from_diagonal(&Vector3::new(
+                        mass * (y * y + z * z) * twelfth,
+                        mass * (x * x + z * z) * twelfth,
+                        mass * (x * x + y * y) * twelfth,
+                    )),
+                )
+            }
+            Shape::Cylinder {
+                radius,
+                inner_radius,
+                height,
+            } => {
+                let (r2, ri2, h) = (
+                    real::<T>(radius * radius),
+                    real::<T>(inner_radius * inner_radius),
+                    real::<T>(height),
+                );
+                let mass = density * pi * (r2 - ri2) * h;
+                let transverse = mass * (real::<T>(3.0) * (r2 + ri2) + h * h) * twelfth;
+
+                MassProperties::new(
+                    mass,
+                    Vector3::new(T::zero(), h * half, T::zero()),
+                    Matrix3::from_diagonal(&Vector3::new(
+                        transverse,
+                        mass * (r2 + ri2) * half,
+                        transverse,
+                    )),
+                )
+            }
+            Shape::Cone { radius, height } => {
+                let (r2, h) = (real::<T>(radius * radius), real::<T>(height));
+                let mass = density * pi * r2 * h / real(3.0);
+                let transverse = mass * (r2 * real(3.0 / 20.0) + h * h * real(3.0 / 80.0));
+
+                MassProperties::new(
+                    mass,
+                    Vector3::new(T::zero(), h * real(0.75), T::zero()),
+                    Matrix3::from_diagonal(&Vector3::new(
+                        transverse,
+                        mass * r2 * real(0.3),
+                        transverse,
+                    )),
+                )
+            }
+            Shape::Ellipsoid { x, y, z } => {
+                let (a, b, c) = (real::<T>(x), real::<T>(y), real::<T>(z));
+                let mass = density * pi * a * b * c * real(4.0 / 3.0);
+                let fifth = mass / real(5.0);
+
+                MassProperties::new(
+                    mass,
+                    Vector3::new(T::zero(), b, T::zero()),
+                    Matrix3::from_diagonal(&Vector3::new(
+                        fifth * (b * b + c * c),
+                        fifth * (a * a + c * c),
+                        fifth * (a * a + b * b),
+                    )),
+                )
+            }
+            Shape::Mesh(ref mesh) => mesh.mass_properties(density),
+            Shape::Disc {
+                radius,
+                thickness,
+                stem,
+            } => {
+                let r2 = real::<T>(radius * radius);
+                let mass = density * pi * r2 * real(thickness);
+
+                MassProperties::new(
+                    mass,
+                    Vector3::new(T::zero(), real(stem), T::zero()),
+                    Matrix3::from_diagonal(&Vector3::new(
+                        mass * r2 / real(4.0),
+                        mass * r2 * half,
+                        mass * r2 / real(4.0),
+                    )),
+                )
+            }
+        }
+    }
+
+    pub fn tip<T: Real>(&self) -> Vector3<T> {
+        match *self {
+            Shape::Cube { size } => Vector3::new(real(size), real(size), real(size)),
+            Shape::Cuboid { x, y, z } => Vector3::new(real(x), real(y), real(z)),
+            Shape::Cylinder { height, .. } | Shape::Cone { height, .. } => {
+                Vector3::new(T::zero(), real(height), T::zero())
+            }
+            Shape::Ellipsoid { y, .. } => Vector3::new(T::zero(), real(2.0 * y), T::zero()),
+            Shape::Disc { stem, .. } => Vector3::new(T::zero(), real(stem), T::zero()),
+            Shape::Mesh(ref mesh) => mesh.tip(),
+        }
+    }
+
+    pub fn box_extent(&self) -> Option<Vector3<f64>> {
+        match *self {
+            Shape::Cube { size } => Some(Vector3::repeat(size)),
+            Shape::Cuboid { x, y, z } => Some(Vector3::new(x, y, z)),
+            _ => None,
+        }
+    }
+
+    pub fn model_scaling(&self) -> Vector3<f32> {
+        self.box_extent()
+            .map_or_else(|| Vector3::repeat(1.0), |extent| extent.cast())
+    }
+
+    // Box shapes are drawn by scaling the unit cube of `CubeDrawer`, everything else
+    // gets its own mesh in real units.
+    pub fn mesh(&self) -> Option<Mesh> {
+        match *self {
+            Shape::Cube { .. } | Shape::Cuboid { .. } => None,
+            Shape::Cylinder {
+                radius,
+                inner_radius,
+                height,
+            } => Some(Mesh::cylinder(
+                radius as f32,
+                inner_radius as f32,
+                height as f32,
+            )),
+            Shape::Cone { radius, height } => Some(Mesh::cone(radius as f32, height as f32)),
+            Shape::Ellipsoid { x, y, z } => Some(Mesh::ellipsoid(x as f32, y as f32, z as f32)),
+            Shape::Disc {
+                radius,
+                thickness,
+                stem,
+            } => Some(Mesh::disc(radius as f32, thickness as f32, stem as f32)),
+            Shape::Mesh(ref mesh) => Some(mesh.mesh().clone()),
+        }
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Shape::Cube { .. } => "cube",
+            Shape::Cuboid { .. } => "cuboid",
+            Shape::Cylinder { .. } => "cylinder",
+            Shape::Cone { .. } => "cone",
+            Shape::Ellipsoid { .. } => "ellipsoid",
+            Shape::Disc { .. } => "disc",
+            Shape::Mesh(_) => "mesh",
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use egui::{Color32, DragValue, Ui, Widget};
+
+use crate::{imported_mesh::ImportedMesh, shape::Shape};
+
+#[derive(Default)]
+pub struct ShapeEditor {
+    mesh_path: String,
+    mesh_error: Option<String>,
+}
+
+impl ShapeEditor {
+    pub fn ui(&mut self, ui: &mut Ui, shape: &mut Shape) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("shape")
+                .selected_text(shape.to_string())
+                .show_ui(ui, |ui| {
+                    for default in Shape::DEFAULTS {
+                        if ui
+                            .selectable_label(shape.same_kind(&default), default.to_string())
+                            .clicked()
+                            && !shape.same_kind(&default)
+                        {
+                            *shape = default;
+                            changed = true;
+                        }
+                    }
+                });
+
+            ui.label("shape");
+        });
+
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(&mut self.mesh_path);
+
+            if ui.button("load mesh").clicked() {
+                match ImportedMesh::load(&self.mesh_path) {
+                    Ok(mesh) => {
+                        *shape = Shape::Mesh(Arc::new(mesh));
+                        self.mesh_error = None;
+                        changed = true;
+                    }
+                    Err(error) => self.mesh_error = Some(error.to_string()),
+                }
+            }
+        });
+
+        if let Some(error) = &self.mesh_error {
+            ui.colored_label(Color32::RED, error);
+        }
+
+        let mut length = |ui: &mut Ui, value: &mut f64, min: f64, label: &str| {
+            ui.horizontal(|ui| {
+                changed |= DragValue::new(value)
+                    .clamp_range(min..=10.0)
+                    .speed(0.01)
+                    .ui(ui)
+                    .changed();
+
+                ui.label(label);
+            });
+        };
+
+        match shape {
+            Shape::Cube { size } => length(ui, size, 0.1, "cube size"),
+            Shape::Cuboid { x, y, z } => {
+                length(ui, x, 0.01, "x edge");
+                length(ui, y, 0.01, "y edge");
+                length(ui, z, 0.01, "z edge");
+            }
+            Shape::Cylinder {
+                radius,
+                inner_radius,
+                height,
+            } => {
+                length(ui, radius, 0.01, "radius");
+                length(ui, inner_radius, 0.0, "inner radius");
+                length(ui, height, 0.01, "height");
+                *inner_radius = inner_radius.min(*radius * 0.99);
+            }
+            Shape::Cone { radius, height } => {
+                length(ui, radius, 0.01, "radius");
+                length(ui, height, 0.01, "height");
+            }
+            Shape::Ellipsoid { x, y, z } => {
+                length(ui, x, 0.01, "x semi-axis");
+                length(ui, y, 0.01, "y semi-axis");
+                length(ui, z, 0.01, "z semi-axis");
+            }
+            Shape::Disc {
+                radius,
+                thickness,
+                stem,
+            } => {
+                length(ui, radius, 0.01, "radius");
+                length(ui, thickness, 0.001, "thickness");
+                length(ui, stem, 0.01, "stem length");
+            }
+            Shape::Mesh(mesh) => {
+                let properties = mesh.mass_properties(1.0f64);
+
+                ui.label(format!("mesh: {}", mesh.path().display()));
+                ui.label(format!("volume: {:.4}", properties.mass));
+                ui.label(format!(
+                    "centre of mass: ({:.3}, {:.3}, {:.3})",
+                    properties.center.x, properties.center.y, properties.center.z
+                ));
+                if !mesh.watertight() {
+                    ui.colored_label(
+                        Color32::YELLOW,
+                        "mesh is not watertight, mass properties are unreliable",
+                    );
+                }
+            }
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,206 @@
+use derive_new::new;
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    diagnostics::{Diagnostics, Drift},
+    integrator::Integrator,
+    real::{real, Real},
+    rigid_body::RigidBody,
+    step_controller::AdaptiveStepController,
+    top_dynamics::TopDynamics,
+};
+
+#[derive(Debug, Clone, Copy, new, Serialize, Deserialize)]
+pub struct TopState<T: Real> {
+    pub q: UnitQuaternion<T>,
+    pub w: Vector3<T>,
+    pub t: T,
+}
+
+impl<T: Real> TopState<T> {
+    pub fn cast<U: Real>(&self) -> TopState<U> {
+        TopState::new(
+            UnitQuaternion::new_unchecked(Quaternion::from(self.q.coords.map(Real::cast))),
+            self.w.map(Real::cast),
+            self.t.cast(),
+        )
+    }
+}
+
+pub struct Simulator<T: Real> {
+    dynamics: TopDynamics<T>,
+    integrator: Box<dyn Integrator<T>>,
+    top: Vector3<T>,
+    integration_step: T,
+    step_controller: Option<AdaptiveStepController<T>>,
+    state: TopState<T>,
+    steps: u64,
+    quaternion_norm: T,
+    initial_diagnostics: Diagnostics<T>,
+}
+
+impl<T: Real> Simulator<T> {
+    pub fn new(
+        body: &RigidBody<T>,
+        integrator: Box<dyn Integrator<T>>,
+        integration_step: T,
+        state: TopState<T>,
+    ) -> Self {
+        let dynamics = TopDynamics::new(body);
+        let initial_diagnostics = dynamics.diagnostics(&state, T::one());
+        let mut simulator = Self {
+            dynamics,
+            integrator,
+            top: body.tip(),
+            integration_step,
+            step_controller: None,
+            state,
+            steps: 0,
+            quaternion_norm: T::one(),
+            initial_diagnostics,
+        };
+        // A run may start part-way along the grid, e.g. when branched from an earlier one.
+        simulator.steps = simulator.grid_steps();
+
+        simulator
+    }
+
+    pub fn state(&self) -> &TopState<T> {
+        &self.state
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn step_controller(&self) -> Option<&AdaptiveStepController<T>> {
+        self.step_controller.as_ref()
+    }
+
+    pub fn set_step_controller(&mut self, step_controller: Option<AdaptiveStepController<T>>) {
+        self.step_controller = step_controller;
+    }
+
+    pub fn diagnostics(&self) -> Diagnostics<T> {
+        self.dynamics.diagnostics(&self.state, self.quaternion_norm)
+    }
+
+    pub fn drift(&self) -> Drift<T> {
+        self.diagnostics().drift_from(&self.initial_diagnostics)
+    }
+
+    pub fn set_gravity(&mut self, gravity: bool) {
+        if self.dynamics.gravity() != gravity {
+            self.dynamics.set_gravity(gravity);
+            // Toggling gravity changes what is conserved, so drift is measured from here on.
+            self.initial_diagnostics = self.diagnostics();
+        }
+    }
+
+    pub fn tip_position(&self) -> Vector3<T> {
+        self.state.q.to_rotation_matrix() * self.top
+    }
+
+    // Advances to the next point of the fixed grid t = n·h, so the states visited do not depend
+    // on how the caller paces the calls.
+    pub fn advance_step(&mut self) {
+        let steps = self.steps + 1;
+        self.integrate_to(self.integration_step * real(steps as f64));
+        self.steps = steps;
+    }
+
+    pub fn advance_to(&mut self, t: T) {
+        self.integrate_to(t);
+        self.steps = self.grid_steps();
+    }
+
+    pub fn step(&mut self, h: T) {
+        self.take_step(h);
+        self.steps = self.grid_steps();
+    }
+
+    // The last point of the grid at or before the current time, within the tolerance
+    // `integrate_to` stops short by. A state that has gone non-finite keeps its count.
+    fn grid_steps(&self) -> u64 {
+        let steps = self.state.t.to_f64() / self.integration_step.to_f64() + 1e-3;
+        if steps.is_finite() {
+            steps.floor().max(0.0) as u64
+        } else {
+            self.steps
+        }
+    }
+
+    fn integrate_to(&mut self, t: T) {
+        let tolerance =
+            (self.integration_step * real(1e-3)).max(t.abs() * T::default_epsilon() * real(4.0));
+
+        if let (Some(controller), Some(order)) = (
+            self.step_controller.as_mut(),
+            self.integrator.embedded_order(),
+        ) {
+            while t - self.state.t > tolerance {
+                let h = controller.proposed_step().min(t - self.state.t);
+                let result = self.integrator.step(&self.dynamics, &self.state, h);
+                let accepted = match result.error {
+                    Some(error) => {
+                        controller.evaluate(&self.state, &result.state, &error, h, order)
+                    }
+                    None => true,
+                };
+
+                if accepted {
+                    self.state = result.state;
+                    self.quaternion_norm = result.quaternion_norm;
+                }
+            }
+
+            return;
+        }
+
+        while self.state.t + self.integration_step <= t + tolerance {
+            self.take_step(self.integration_step);
+        }
+
+        if t - self.state.t > tolerance {
+            self.take_step(t - self.state.t);
+        }
+    }
+
+    fn take_step(&mut self, h: T) {
+        let result = self.integrator.step(&self.dynamics, &self.state, h);
+        self.state = result.state;
+        self.quaternion_norm = result.quaternion_norm;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{config::Config, real::Precision, simulation_parameters::SimulationParameters};
+
+    // The step count follows the time however the simulator was advanced, so the next fixed
+    // step always lands on the grid point after the current time.
+    #[test]
+    fn step_count_follows_advance_to() {
+        let parameters = SimulationParameters {
+            integration_step: 0.01,
+            precision: Precision::Double,
+            ..Config::default().simulation_parameters()
+        };
+        let mut simulator = parameters.build_simulator::<f64>();
+
+        simulator.advance_to(0.0105);
+        assert_eq!(simulator.steps(), 1);
+        simulator.advance_step();
+        assert_eq!(simulator.steps(), 2);
+        assert!((simulator.state().t - 0.02).abs() < 1e-12);
+
+        simulator.advance_to(0.05);
+        assert_eq!(simulator.steps(), 5);
+        simulator.step(0.004);
+        assert_eq!(simulator.steps(), 5);
+        simulator.advance_step();
+        assert_eq!(simulator.steps(), 6);
+        assert!((simulator.state().t - 0.06).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+// Longest stretch of stepping between two publications, so the view and the controls stay
+// responsive whatever the rate.
+pub const FRAME: Duration = Duration::from_millis(16);
+const FACTOR_WINDOW: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ClockMode {
+    RealTime,
+    SlowMotion(f64),
+    FastForward(f64),
+    AsFastAsPossible,
+}
+
+impl ClockMode {
+    pub const KINDS: [ClockMode; 4] = [
+        ClockMode::RealTime,
+        ClockMode::SlowMotion(0.1),
+        ClockMode::FastForward(10.0),
+        ClockMode::AsFastAsPossible,
+    ];
+
+    pub fn same_kind(&self, other: &ClockMode) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    // Simulated seconds per wall-clock second, None when unpaced.
+    pub fn rate(&self) -> Option<f64> {
+        match *self {
+            ClockMode::RealTime => Some(1.0),
+            ClockMode::SlowMotion(rate) => Some(rate.clamp(0.01, 1.0)),
+            ClockMode::FastForward(rate) => Some(rate.clamp(2.0, 100.0)),
+            ClockMode::AsFastAsPossible => None,
+        }
+    }
+}
+
+impl Display for ClockMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClockMode::RealTime => write!(f, "real time"),
+            ClockMode::SlowMotion(_) => write!(f, "slow motion"),
+            ClockMode::FastForward(_) => write!(f, "fast forward"),
+            ClockMode::AsFastAsPossible => write!(f, "as fast as possible"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClockReadout {
+    pub simulated_time: f64,
+    pub real_time_factor: Option<f64>,
+}
+
+// Paces a simulation that advances in fixed integration steps. The clock only decides when
+// the next step is taken, never its size, so the results do not depend on the machine.
+pub struct SimulationClock {
+    mode: ClockMode,
+    step: f64,
+    // Wall-clock instant at which the simulation was at the given step.
+    anchor: (Instant, u64),
+    frame_start: Instant,
+    window: (Instant, u64),
+    readout: ClockReadout,
+}
+
+impl SimulationClock {
+    pub fn new(mode: ClockMode, step: f64, steps: u64) -> Self {
+        let now = Instant::now();
+
+        Self {
+            mode,
+            step,
+            anchor: (now, steps),
+            frame_start: now,
+            window: (now, steps),
+            readout: ClockReadout::default(),
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: ClockMode, steps: u64) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.anchor = (Instant::now(), steps);
+        }
+    }
+
+    // Keeps the clock from running ahead while the simulation is held, e.g. paused.
+    pub fn hold(&mut self, steps: u64) {
+        self.anchor = (Instant::now(), steps);
+        self.window = (Instant::now(), steps);
+        self.readout.real_time_factor = None;
+        self.readout.simulated_time = steps as f64 * self.step;
+    }
+
+    pub fn readout(&self) -> ClockReadout {
+        self.readout
+    }
+
+    fn due(&self, rate: f64, steps: u64) -> Instant {
+        let (instant, anchor_steps) = self.anchor;
+
+        instant + Duration::from_secs_f64((steps - anchor_steps) as f64 * self.step / rate)
+    }
+
+    pub fn start_frame(&mut self) {
+        self.frame_start = Instant::now();
+    }
+
+    // Whether step number `steps + 1` is due within the current frame.
+    pub fn step_due(&self, steps: u64) -> bool {
+        let now = Instant::now();
+
+        if now - self.frame_start > FRAME {
+            return false;
+        }
+
+        match self.mode.rate() {
+            Some(rate) => self.due(rate, steps + 1) <= now,
+            None => true,
+        }
+    }
+
+    // Updates the readout and sleeps until the next step is due, at most for a frame.
+    pub fn end_frame(&mut self, steps: u64) {
+        let now = Instant::now();
+
+        let (window_start, window_steps) = self.window;
+        if now - window_start >= FACTOR_WINDOW {
+            self.readout.real_time_factor = Some(
+                (steps - window_steps) as f64 * self.step / (now - window_start).as_secs_f64(),
+            );
+            self.window = (now, steps);
+        }
+        self.readout.simulated_time = steps as f64 * self.step;
+
+        if let Some(rate) = self.mode.rate() {
+            let due = self.due(rate, steps + 1);
+
+            // Falling more than a frame behind means the machine cannot keep up; carry on
+            // from here at a lower achieved rate rather than trying to catch up in a burst.
+            if now > due + FRAME {
+                self.anchor = (now, steps);
+            } else if due > now {
+                std::thread::sleep((due - now).min(FRAME));
+            }
+        }
+    }
+}
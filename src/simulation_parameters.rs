@@ -0,0 +1,119 @@
+use derive_new::new;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    body_definition::BodyDefinition,
+    config::ConfigError,
+    initial_conditions::InitialConditions,
+    integrator::IntegratorKind,
+    real::{real, Precision, Real},
+    rigid_body::RigidBody,
+    simulation::{Simulator, TopState},
+    step_controller::AdaptiveStepController,
+};
+
+#[derive(Debug, Clone, Copy, new, Serialize, Deserialize)]
+pub struct StepControllerSettings {
+    pub absolute_tolerance: f64,
+    pub relative_tolerance: f64,
+    pub min_step: f64,
+    pub max_step: f64,
+}
+
+impl StepControllerSettings {
+    pub fn is_valid(&self) -> bool {
+        self.controller::<f64>(self.min_step).is_some()
+    }
+
+    fn controller<T: Real>(&self, initial_step: f64) -> Option<AdaptiveStepController<T>> {
+        AdaptiveStepController::new(
+            real(self.absolute_tolerance),
+            real(self.relative_tolerance),
+            real(self.min_step),
+            real(self.max_step),
+            real(initial_step),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationParameters {
+    pub body: BodyDefinition,
+    pub initial: InitialConditions,
+    pub integration_step: f64,
+    pub integrator: IntegratorKind,
+    pub step_controller: Option<StepControllerSettings>,
+    pub gravity: bool,
+    pub precision: Precision,
+    pub analytic_reference: bool,
+    // The run finishes here; None runs until stopped.
+    pub end_time: Option<f64>,
+    // Continues from this state instead of the initial conditions, e.g. to branch a run.
+    pub start_state: Option<TopState<f64>>,
+}
+
+impl SimulationParameters {
+    // Rejects what would panic building the dynamics or never get through a step, so every
+    // loader can turn it away before a run starts.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |reason: &str| Err(ConfigError::Invalid(reason.to_string()));
+
+        if !self.body.is_physical() {
+            return invalid(
+                "the body's dimensions, density and mass must be positive and its pivot on the shape",
+            );
+        }
+        if !(self.integration_step.is_finite() && self.integration_step > 0.0) {
+            return invalid("the integration step must be positive");
+        }
+        if self
+            .end_time
+            .is_some_and(|end_time| !(end_time.is_finite() && end_time >= 0.0))
+        {
+            return invalid("the end time must be finite and not negative");
+        }
+        if self
+            .step_controller
+            .is_some_and(|settings| !settings.is_valid())
+        {
+            return invalid(
+                "the step controller needs positive tolerances and 0 < min_step <= max_step",
+            );
+        }
+        if self.start_state.is_some_and(|state| {
+            !(state.t.is_finite()
+                && state.q.coords.iter().all(|x| x.is_finite())
+                && state.w.iter().all(|x| x.is_finite()))
+        }) {
+            return invalid("the start state must be finite");
+        }
+
+        Ok(())
+    }
+
+    pub fn build_body<T: Real>(&self) -> RigidBody<T> {
+        self.body.build()
+    }
+
+    pub fn build_simulator<T: Real>(&self) -> Simulator<T> {
+        let mut simulator = Simulator::new(
+            &self.build_body(),
+            self.integrator.create(),
+            real(self.integration_step),
+            self.start_state
+                .map_or_else(|| self.initial.state(), |state| state.cast()),
+        );
+
+        // Settings the controller refuses leave the run on the fixed step.
+        if let Some(settings) = self
+            .step_controller
+            .filter(|_| self.integrator.is_embedded())
+        {
+            simulator.set_step_controller(settings.controller(self.integration_step));
+        }
+
+        simulator.set_gravity(self.gravity);
+
+        simulator
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::Display;
+
+// Idle → Running ⇄ Paused → Finished once the end time is reached; Stop returns to Idle from
+// anywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SimulationState {
+    #[default]
+    Idle,
+    Running,
+    Paused,
+    Finished,
+}
+
+impl SimulationState {
+    // Whether a simulated state is on screen, rather than a preview of the editors.
+    pub fn is_active(&self) -> bool {
+        *self != SimulationState::Idle
+    }
+}
+
+impl Display for SimulationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SimulationState::Idle => write!(f, "idle"),
+            SimulationState::Running => write!(f, "running"),
+            SimulationState::Paused => write!(f, "paused"),
+            SimulationState::Finished => write!(f, "finished"),
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use concurrent_queue::ConcurrentQueue;
+use nalgebra::{UnitQuaternion, Vector3};
+
+use crate::{
+    analytic_solution::AnalyticSolution,
+    diagnostics::{Diagnostics, Drift},
+    history::History,
+    precession_analyser::{PrecessionAnalyser, PrecessionReadout},
+    real::{quaternion_to_f32, vector_to_f32, Precision, Real},
+    simulation::Simulator,
+    simulation_clock::{ClockMode, ClockReadout, SimulationClock, FRAME},
+    simulation_parameters::SimulationParameters,
+    simulation_state::SimulationState,
+    step_controller::AdaptiveStepController,
+};
+
+pub type DiagnosticsReadout = (Diagnostics<f64>, Drift<f64>);
+
+const ANALYTIC_ERROR_CAPACITY: usize = 4096;
+
+#[derive(Clone)]
+pub struct SharedSimulation {
+    pub rotation: Arc<Mutex<UnitQuaternion<f32>>>,
+    pub state: Arc<Mutex<SimulationState>>,
+    pub pending_steps: Arc<Mutex<u64>>,
+    pub gravity: Arc<Mutex<bool>>,
+    pub step_controller: Arc<Mutex<Option<AdaptiveStepController<f64>>>>,
+    pub diagnostics: Arc<Mutex<Option<DiagnosticsReadout>>>,
+    pub trajectory_queue: Arc<ConcurrentQueue<Vector3<f32>>>,
+    pub analytic_reference: Arc<Mutex<Option<String>>>,
+    pub analytic_error: Arc<Mutex<Vec<[f64; 2]>>>,
+    pub precession: Arc<Mutex<Option<PrecessionReadout>>>,
+    pub clock_mode: Arc<Mutex<ClockMode>>,
+    pub clock: Arc<Mutex<Option<ClockReadout>>>,
+    pub history: Arc<Mutex<History>>,
+}
+
+impl SharedSimulation {
+    pub fn new() -> Self {
+        Self {
+            rotation: Arc::new(Mutex::new(UnitQuaternion::identity())),
+            state: Arc::new(Mutex::new(SimulationState::Idle)),
+            pending_steps: Arc::new(Mutex::new(0)),
+            gravity: Arc::new(Mutex::new(true)),
+            step_controller: Arc::new(Mutex::new(None)),
+            diagnostics: Arc::new(Mutex::new(None)),
+            trajectory_queue: Arc::new(ConcurrentQueue::unbounded()),
+            analytic_reference: Arc::new(Mutex::new(None)),
+            analytic_error: Arc::new(Mutex::new(Vec::new())),
+            precession: Arc::new(Mutex::new(None)),
+            clock_mode: Arc::new(Mutex::new(ClockMode::RealTime)),
+            clock: Arc::new(Mutex::new(None)),
+            history: Arc::new(Mutex::new(History::new(1))),
+        }
+    }
+
+    pub fn state(&self) -> SimulationState {
+        *self.state.lock().unwrap()
+    }
+
+    // Moves to `to` only from one of the expected states, so a request from the UI cannot
+    // override a transition the simulation thread has made in the meantime.
+    pub fn transition(&self, from: &[SimulationState], to: SimulationState) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let allowed = from.contains(&state);
+        if allowed {
+            *state = to;
+        }
+
+        allowed
+    }
+
+    fn publish<T: Real>(&self, simulator: &Simulator<T>) {
+        *self.rotation.lock().unwrap() = quaternion_to_f32(&simulator.state().q);
+        *self.step_controller.lock().unwrap() = simulator
+            .step_controller()
+            .map(AdaptiveStepController::cast);
+        *self.diagnostics.lock().unwrap() =
+            Some((simulator.diagnostics().cast(), simulator.drift().cast()));
+    }
+
+    fn publish_reference(&self, reference: Option<&AnalyticSolution>) {
+        *self.analytic_reference.lock().unwrap() = Some(reference.map_or_else(
+            || "no closed-form solution for this body and state".to_string(),
+            AnalyticSolution::to_string,
+        ));
+    }
+
+    // Returns true when the history was halved to stay within capacity.
+    fn record_analytic_error(&self, t: f64, error: f64) -> bool {
+        let mut history = self.analytic_error.lock().unwrap();
+        history.push([t, error]);
+
+        if history.len() <= ANALYTIC_ERROR_CAPACITY {
+            return false;
+        }
+
+        let mut index = 0;
+        history.retain(|_| {
+            index += 1;
+            index % 2 == 1
+        });
+
+        true
+    }
+}
+
+impl Default for SharedSimulation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn spawn_simulation(
+    parameters: SimulationParameters,
+    shared: SharedSimulation,
+) -> JoinHandle<()> {
+    *shared.state.lock().unwrap() = SimulationState::Running;
+    *shared.pending_steps.lock().unwrap() = 0;
+    *shared.analytic_reference.lock().unwrap() = None;
+    *shared.clock.lock().unwrap() = None;
+    shared.analytic_error.lock().unwrap().clear();
+
+    let mut history = shared.history.lock().unwrap();
+    match parameters.start_state {
+        Some(state) => history.truncate_from(state.t),
+        None => history.clear(),
+    }
+    drop(history);
+
+    match parameters.precision {
+        Precision::Single => spawn_with::<f32>(parameters, shared),
+        Precision::Double => spawn_with::<f64>(parameters, shared),
+    }
+}
+
+fn spawn_with<T: Real>(
+    parameters: SimulationParameters,
+    shared: SharedSimulation,
+) -> JoinHandle<()> {
+    let mut simulator = parameters.build_simulator::<T>();
+    shared.publish(&simulator);
+    let mut history = shared.history.lock().unwrap();
+    history.record(
+        simulator.steps(),
+        &simulator.state().cast(),
+        simulator.diagnostics().quaternion_norm_error.cast(),
+    );
+    history.record_gravity(simulator.state().t.cast(), parameters.gravity);
+    drop(history);
+    let mut recorded_gravity = parameters.gravity;
+
+    let reference_body = parameters.build_body::<f64>();
+    let mut reference_gravity = parameters.gravity;
+    let mut reference = None;
+    let mut analyser = PrecessionAnalyser::new(&reference_body);
+    if parameters.analytic_reference {
+        reference = AnalyticSolution::new(
+            &reference_body,
+            &simulator.state().cast(),
+            reference_gravity,
+        );
+        shared.publish_reference(reference.as_ref());
+    }
+
+    thread::spawn(move || {
+        let mut clock = SimulationClock::new(
+            *shared.clock_mode.lock().unwrap(),
+            parameters.integration_step,
+            simulator.steps(),
+        );
+        let end_step = parameters
+            .end_time
+            .map(|end_time| (end_time / parameters.integration_step).round() as u64);
+        let mut error_stride = 8u64;
+
+        let mut advance = |simulator: &mut Simulator<T>, gravity: bool| {
+            shared
+                .trajectory_queue
+                .push(vector_to_f32(&simulator.tip_position()))
+                .unwrap();
+
+            simulator.advance_step();
+
+            let state = simulator.state().cast::<f64>();
+            analyser.record(&state, gravity);
+            shared.history.lock().unwrap().record(
+                simulator.steps(),
+                &state,
+                simulator.diagnostics().quaternion_norm_error.cast(),
+            );
+
+            if parameters.analytic_reference {
+                // A different force field needs a new reference, started from where we are.
+                if gravity != reference_gravity {
+                    reference_gravity = gravity;
+                    reference = AnalyticSolution::new(&reference_body, &state, gravity);
+                    shared.publish_reference(reference.as_ref());
+                }
+
+                if let Some(reference) = reference
+                    .as_ref()
+                    .filter(|_| simulator.steps().is_multiple_of(error_stride))
+                {
+                    let error = state.q.angle_to(&reference.orientation(state.t));
+                    if shared.record_analytic_error(state.t, error) {
+                        error_stride *= 2;
+                    }
+                }
+            }
+
+            *shared.precession.lock().unwrap() = Some(analyser.readout());
+        };
+        let finished =
+            |simulator: &Simulator<T>| end_step.is_some_and(|end| simulator.steps() >= end);
+
+        loop {
+            let gravity = *shared.gravity.lock().unwrap();
+            simulator.set_gravity(gravity);
+            if gravity != recorded_gravity {
+                recorded_gravity = gravity;
+                shared
+                    .history
+                    .lock()
+                    .unwrap()
+                    .record_gravity(simulator.state().t.cast(), gravity);
+            }
+
+            match shared.state() {
+                SimulationState::Running => {
+                    clock.set_mode(*shared.clock_mode.lock().unwrap(), simulator.steps());
+
+                    clock.start_frame();
+                    while clock.step_due(simulator.steps()) && !finished(&simulator) {
+                        advance(&mut simulator, gravity);
+                    }
+
+                    clock.end_frame(simulator.steps());
+                }
+                SimulationState::Paused => {
+                    let steps = std::mem::take(&mut *shared.pending_steps.lock().unwrap());
+                    for _ in 0..steps {
+                        if finished(&simulator) {
+                            break;
+                        }
+                        advance(&mut simulator, gravity);
+                    }
+
+                    clock.hold(simulator.steps());
+                    if steps == 0 {
+                        thread::sleep(FRAME);
+                    }
+                }
+                SimulationState::Idle | SimulationState::Finished => break,
+            }
+
+            shared.publish(&simulator);
+            *shared.clock.lock().unwrap() = Some(clock.readout());
+
+            if finished(&simulator) {
+                shared.transition(
+                    &[SimulationState::Running, SimulationState::Paused],
+                    SimulationState::Finished,
+                );
+            }
+        }
+    })
+}
@@ -0,0 +1,89 @@
+use nalgebra::{Complex, Matrix3, Matrix6, Vector3};
+
+use crate::{initial_conditions::InitialConditions, rigid_body::RigidBody};
+
+// Stability of the "sleeping" top: the centre of mass straight above the pivot and the body
+// spinning about that axis.
+//
+// The motion is linearised in the Euler–Poisson variables (γ, ω), γ being the world up axis
+// seen from the body, in which the steady spin is a fixed point:
+//
+//   γ' = γ × ω
+//   Iω' = (Iω) × ω - m g c × γ
+//
+// Two eigenvalues are always zero (|γ| = 1 and L·γ are conserved); a positive real part
+// anywhere else is a growth rate of the perturbation.
+#[derive(Debug, Clone)]
+pub struct StabilityAnalysis {
+    pub spin: f64,
+    // Classic criterion ω² > 4 m g l I1 / I3², with I1 the larger transverse moment.
+    pub critical_spin: Option<f64>,
+    // |ω'| at the linearisation point; non-zero when the spin axis is not principal.
+    pub equilibrium_residual: f64,
+    pub eigenvalues: Vec<Complex<f64>>,
+}
+
+impl StabilityAnalysis {
+    pub fn new(body: &RigidBody<f64>, initial: &InitialConditions, gravity: bool) -> Self {
+        let center_of_mass = body.center_of_mass();
+        let inertia = body.moment_of_interia();
+        let weight = if gravity { body.mass() * 9.81 } else { 0.0 };
+
+        // Without an arm the top has no preferred upright axis; take the body's y axis.
+        let axis = center_of_mass
+            .try_normalize(1e-12)
+            .unwrap_or_else(Vector3::y);
+        let spin = initial.state::<f64>().w.dot(&axis);
+        let w = axis * spin;
+        let inverse = inertia.try_inverse().unwrap_or_else(Matrix3::zeros);
+
+        let critical_spin = (weight > 0.0 && center_of_mass.norm() > 1e-12).then(|| {
+            let axial = axis.dot(&(inertia * axis));
+            let projection = Matrix3::identity() - axis * axis.transpose();
+            let transverse = (projection * inertia * projection)
+                .symmetric_eigenvalues()
+                .max();
+
+            (4.0 * weight * center_of_mass.norm() * transverse).sqrt() / axial
+        });
+
+        let angular_momentum = inertia * w;
+        let equilibrium_residual = (inverse * angular_momentum.cross(&w)).norm();
+
+        let mut jacobian = Matrix6::zeros();
+        jacobian
+            .fixed_view_mut::<3, 3>(0, 0)
+            .copy_from(&-w.cross_matrix());
+        jacobian
+            .fixed_view_mut::<3, 3>(0, 3)
+            .copy_from(&axis.cross_matrix());
+        jacobian
+            .fixed_view_mut::<3, 3>(3, 0)
+            .copy_from(&(inverse * center_of_mass.cross_matrix() * -weight));
+        jacobian
+            .fixed_view_mut::<3, 3>(3, 3)
+            .copy_from(&(inverse * (angular_momentum.cross_matrix() - w.cross_matrix() * inertia)));
+
+        let mut eigenvalues: Vec<_> = jacobian.complex_eigenvalues().iter().copied().collect();
+        eigenvalues.sort_by(|a, b| b.re.total_cmp(&a.re).then(b.im.total_cmp(&a.im)));
+
+        Self {
+            spin,
+            critical_spin,
+            equilibrium_residual,
+            eigenvalues,
+        }
+    }
+
+    pub fn growth_rate(&self) -> f64 {
+        self.eigenvalues
+            .iter()
+            .map(|eigenvalue| eigenvalue.re)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    pub fn below_critical(&self) -> bool {
+        self.critical_spin
+            .is_some_and(|critical| self.spin.abs() < critical)
+    }
+}
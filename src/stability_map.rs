@@ -0,0 +1,220 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use derive_new::new;
+
+use crate::{
+    initial_conditions::InitialConditions,
+    real::{Precision, Real},
+    simulation_parameters::SimulationParameters,
+};
+
+pub const CELL_PIXELS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, new)]
+pub struct StabilityMapSettings {
+    pub deviations: [f64; 2],
+    pub deviation_count: usize,
+    pub spins: [f64; 2],
+    pub spin_count: usize,
+    pub duration: f64,
+}
+
+impl Default for StabilityMapSettings {
+    fn default() -> Self {
+        Self::new([0.05, 1.5], 16, [0.0, 60.0], 24, 5.0)
+    }
+}
+
+impl StabilityMapSettings {
+    fn value(range: [f64; 2], count: usize, index: usize) -> f64 {
+        if count < 2 {
+            return range[0];
+        }
+
+        range[0] + (range[1] - range[0]) * index as f64 / (count - 1) as f64
+    }
+
+    pub fn deviation(&self, index: usize) -> f64 {
+        Self::value(self.deviations, self.deviation_count, index)
+    }
+
+    pub fn spin(&self, index: usize) -> f64 {
+        Self::value(self.spins, self.spin_count, index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
+    Pending,
+    Stayed,
+    Fell(f64),
+}
+
+// Outcomes on a deviation × spin grid, row-major with one row per deviation.
+#[derive(Debug, Clone)]
+pub struct StabilityMap {
+    pub settings: StabilityMapSettings,
+    pub cells: Vec<Cell>,
+}
+
+impl StabilityMap {
+    pub fn new(settings: StabilityMapSettings) -> Self {
+        Self {
+            settings,
+            cells: vec![Cell::Pending; settings.deviation_count * settings.spin_count],
+        }
+    }
+
+    pub fn completed(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|cell| **cell != Cell::Pending)
+            .count()
+    }
+
+    pub fn cell(&self, deviation: usize, spin: usize) -> Cell {
+        self.cells[deviation * self.settings.spin_count + spin]
+    }
+
+    pub fn color(&self, cell: Cell) -> [u8; 3] {
+        match cell {
+            Cell::Pending => [60, 60, 60],
+            Cell::Stayed => [60, 170, 80],
+            // Tops that fall late are drawn lighter than those that fall at once.
+            Cell::Fell(t) => {
+                let late = (t / self.settings.duration).clamp(0.0, 1.0);
+                [
+                    (120.0 + 135.0 * late) as u8,
+                    (30.0 + 120.0 * late) as u8,
+                    30,
+                ]
+            }
+        }
+    }
+
+    // One CELL_PIXELS square per cell, spin growing to the right and deviation upwards.
+    pub fn pixels(&self) -> ([usize; 2], Vec<u8>) {
+        let size = [
+            self.settings.spin_count * CELL_PIXELS,
+            self.settings.deviation_count * CELL_PIXELS,
+        ];
+        let mut pixels = Vec::with_capacity(size[0] * size[1] * 3);
+
+        for y in 0..size[1] {
+            let deviation = self.settings.deviation_count - 1 - y / CELL_PIXELS;
+            for x in 0..size[0] {
+                pixels.extend(self.color(self.cell(deviation, x / CELL_PIXELS)));
+            }
+        }
+
+        (size, pixels)
+    }
+
+    pub fn write_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "deviation,spin,fallen,fall_time")?;
+
+        for deviation in 0..self.settings.deviation_count {
+            for spin in 0..self.settings.spin_count {
+                let (fallen, fall_time) = match self.cell(deviation, spin) {
+                    Cell::Pending => continue,
+                    Cell::Stayed => (false, String::new()),
+                    Cell::Fell(t) => (true, t.to_string()),
+                };
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    self.settings.deviation(deviation),
+                    self.settings.spin(spin),
+                    fallen,
+                    fall_time
+                )?;
+            }
+        }
+
+        writer.flush()
+    }
+
+    pub fn write_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let ([width, height], pixels) = self.pixels();
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            width as u32,
+            height as u32,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+
+        Ok(writer.finish()?)
+    }
+}
+
+// The top has fallen once its tip drops below the horizontal plane through the pivot. Returns
+// the time of the fall, or None if it stayed up for the whole duration.
+pub fn fall_time(
+    parameters: &SimulationParameters,
+    deviation: f64,
+    spin: f64,
+    duration: f64,
+) -> Option<f64> {
+    let parameters = SimulationParameters {
+        initial: InitialConditions::tilted(deviation, spin),
+        analytic_reference: false,
+        start_state: None,
+        ..parameters.clone()
+    };
+
+    match parameters.precision {
+        Precision::Single => fall_time_with::<f32>(&parameters, duration),
+        Precision::Double => fall_time_with::<f64>(&parameters, duration),
+    }
+}
+
+fn fall_time_with<T: Real>(parameters: &SimulationParameters, duration: f64) -> Option<f64> {
+    let mut simulator = parameters.build_simulator::<T>();
+    let steps = (duration / parameters.integration_step).ceil() as u64;
+
+    while simulator.steps() < steps {
+        simulator.advance_step();
+
+        if simulator.tip_position().y < T::zero() {
+            return Some(simulator.state().t.cast());
+        }
+    }
+
+    None
+}
+
+// Bisects [low, high] for the smallest spin that keeps the top up at this deviation. The top
+// must fall at `low` and stay up at `high`.
+pub fn critical_spin(
+    parameters: &SimulationParameters,
+    deviation: f64,
+    [mut low, mut high]: [f64; 2],
+    duration: f64,
+    tolerance: f64,
+) -> Option<f64> {
+    let stays_up = |spin| fall_time(parameters, deviation, spin, duration).is_none();
+
+    if stays_up(low) || !stays_up(high) {
+        return None;
+    }
+
+    while high - low > tolerance {
+        let middle = 0.5 * (low + high);
+        if stays_up(middle) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+
+    Some(0.5 * (low + high))
+}
@@ -0,0 +1,277 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use egui::{Color32, ColorImage, Context, DragValue, TextureHandle, TextureOptions, Ui, Widget};
+
+use crate::{
+    simulation_parameters::SimulationParameters,
+    stability_map::{
+        critical_spin, fall_time, Cell, StabilityMap, StabilityMapSettings, CELL_PIXELS,
+    },
+};
+
+// Sweeps a grid of tilted initial conditions in a background thread, reusing the body and
+// integrator settings of the main panel.
+#[derive(Default)]
+pub struct StabilityMapWindow {
+    open: bool,
+    settings: StabilityMapSettings,
+    map: Option<Arc<Mutex<StabilityMap>>>,
+    cancel: Arc<Mutex<bool>>,
+    sweep: Option<JoinHandle<()>>,
+    texture: Option<TextureHandle>,
+    export_path: String,
+    message: Option<Result<String, String>>,
+    bisection_deviation: f64,
+    bisection: Option<JoinHandle<Option<f64>>>,
+    critical_spin: Option<Option<f64>>,
+}
+
+impl StabilityMapWindow {
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context, parameters: &SimulationParameters) {
+        let mut open = self.open;
+
+        egui::Window::new("stability map")
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, parameters));
+
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut Ui, parameters: &SimulationParameters) {
+        let running = self
+            .sweep
+            .as_ref()
+            .is_some_and(|sweep| !sweep.is_finished());
+
+        ui.add_enabled_ui(!running, |ui| {
+            range_ui(ui, &mut self.settings.deviations, 0.0..=3.1, "deviation");
+            count_ui(ui, &mut self.settings.deviation_count, "deviation steps");
+            range_ui(ui, &mut self.settings.spins, -100.0..=100.0, "spin");
+            count_ui(ui, &mut self.settings.spin_count, "spin steps");
+
+            ui.horizontal(|ui| {
+                DragValue::new(&mut self.settings.duration)
+                    .clamp_range(0.1..=60.0)
+                    .speed(0.1)
+                    .ui(ui);
+
+                ui.label("duration");
+            });
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!running, egui::Button::new("Run")).clicked() {
+                self.start(parameters.clone());
+            }
+
+            if ui
+                .add_enabled(running, egui::Button::new("Cancel"))
+                .clicked()
+            {
+                *self.cancel.lock().unwrap() = true;
+            }
+        });
+
+        if let Some(map) = self.map.clone() {
+            let map = map.lock().unwrap();
+            ui.label(format!("{} / {} runs", map.completed(), map.cells.len()));
+
+            self.map_ui(ui, &map);
+            if running {
+                ui.ctx().request_repaint();
+            }
+
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut self.export_path);
+
+                if ui.button("export CSV").clicked() {
+                    self.message = Some(
+                        map.write_csv(&self.export_path)
+                            .map(|_| format!("wrote {}", self.export_path))
+                            .map_err(|error| error.to_string()),
+                    );
+                }
+
+                if ui.button("export PNG").clicked() {
+                    self.message = Some(
+                        map.write_png(&self.export_path)
+                            .map(|_| format!("wrote {}", self.export_path))
+                            .map_err(|error| error.to_string()),
+                    );
+                }
+            });
+
+            match &self.message {
+                Some(Ok(message)) => {
+                    ui.label(message);
+                }
+                Some(Err(error)) => {
+                    ui.colored_label(Color32::RED, error);
+                }
+                None => {}
+            }
+        }
+
+        ui.separator();
+        self.bisection_ui(ui, parameters);
+    }
+
+    fn start(&mut self, parameters: SimulationParameters) {
+        let settings = self.settings;
+        let map = Arc::new(Mutex::new(StabilityMap::new(settings)));
+        let cancel = Arc::new(Mutex::new(false));
+
+        self.map = Some(map.clone());
+        self.cancel = cancel.clone();
+        self.message = None;
+
+        self.sweep = Some(thread::spawn(move || {
+            for deviation in 0..settings.deviation_count {
+                for spin in 0..settings.spin_count {
+                    if *cancel.lock().unwrap() {
+                        return;
+                    }
+
+                    let cell = match fall_time(
+                        &parameters,
+                        settings.deviation(deviation),
+                        settings.spin(spin),
+                        settings.duration,
+                    ) {
+                        Some(t) => Cell::Fell(t),
+                        None => Cell::Stayed,
+                    };
+                    map.lock().unwrap().cells[deviation * settings.spin_count + spin] = cell;
+                }
+            }
+        }));
+    }
+
+    fn map_ui(&mut self, ui: &mut Ui, map: &StabilityMap) {
+        let (size, pixels) = map.pixels();
+        let image = ColorImage::from_rgb(size, &pixels);
+
+        let texture = match &mut self.texture {
+            Some(texture) => {
+                texture.set(image, TextureOptions::NEAREST);
+                texture
+            }
+            None => self.texture.insert(ui.ctx().load_texture(
+                "stability map",
+                image,
+                TextureOptions::NEAREST,
+            )),
+        };
+
+        let settings = map.settings;
+        ui.label(format!(
+            "deviation {:.2} … {:.2} (up), spin {:.1} … {:.1} (right)",
+            settings.deviations[0], settings.deviations[1], settings.spins[0], settings.spins[1]
+        ));
+
+        let scale = (360.0 / size[0].max(size[1]) as f32).min(1.0);
+        let response = ui.image((
+            texture.id(),
+            egui::vec2(size[0] as f32 * scale, size[1] as f32 * scale),
+        ));
+
+        if let Some(position) = response.hover_pos() {
+            let cell_size = CELL_PIXELS as f32 * scale;
+            let offset = position - response.rect.min;
+            let spin = ((offset.x / cell_size) as usize).min(settings.spin_count - 1);
+            let deviation = settings.deviation_count
+                - 1
+                - ((offset.y / cell_size) as usize).min(settings.deviation_count - 1);
+
+            response.on_hover_text(format!(
+                "deviation {:.3}, spin {:.2}: {}",
+                settings.deviation(deviation),
+                settings.spin(spin),
+                match map.cell(deviation, spin) {
+                    Cell::Pending => "pending".to_string(),
+                    Cell::Stayed => "stayed up".to_string(),
+                    Cell::Fell(t) => format!("fell at {t:.3} s"),
+                }
+            ));
+        }
+    }
+
+    fn bisection_ui(&mut self, ui: &mut Ui, parameters: &SimulationParameters) {
+        if let Some(bisection) = self.bisection.take_if(|bisection| bisection.is_finished()) {
+            self.critical_spin = Some(bisection.join().unwrap());
+        }
+        let running = self.bisection.is_some();
+
+        ui.horizontal(|ui| {
+            DragValue::new(&mut self.bisection_deviation)
+                .clamp_range(0.0..=3.1)
+                .speed(0.01)
+                .ui(ui);
+
+            ui.label("deviation");
+
+            if ui
+                .add_enabled(!running, egui::Button::new("find critical spin"))
+                .clicked()
+            {
+                let parameters = parameters.clone();
+                let (deviation, spins, duration) = (
+                    self.bisection_deviation,
+                    self.settings.spins,
+                    self.settings.duration,
+                );
+
+                self.critical_spin = None;
+                self.bisection = Some(thread::spawn(move || {
+                    critical_spin(&parameters, deviation, spins, duration, 1e-3)
+                }));
+            }
+        });
+
+        if running {
+            ui.label("bisecting…");
+            ui.ctx().request_repaint();
+        }
+
+        match self.critical_spin {
+            Some(Some(spin)) => {
+                ui.label(format!("critical spin: {spin:.3} rad/s"));
+            }
+            Some(None) => {
+                ui.colored_label(
+                    Color32::YELLOW,
+                    "the spin range does not bracket a fall and a stay",
+                );
+            }
+            None => {}
+        }
+    }
+}
+
+fn range_ui(ui: &mut Ui, range: &mut [f64; 2], limits: std::ops::RangeInclusive<f64>, label: &str) {
+    ui.horizontal(|ui| {
+        for value in range.iter_mut() {
+            DragValue::new(value)
+                .clamp_range(limits.clone())
+                .speed(0.01)
+                .ui(ui);
+        }
+
+        ui.label(label);
+    });
+}
+
+fn count_ui(ui: &mut Ui, count: &mut usize, label: &str) {
+    ui.horizontal(|ui| {
+        DragValue::new(count).clamp_range(1..=128).ui(ui);
+
+        ui.label(label);
+    });
+}
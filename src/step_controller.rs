@@ -0,0 +1,117 @@
+use derive_getters::Getters;
+
+use crate::{
+    integrator::ErrorEstimate,
+    real::{real, Real},
+    simulation::TopState,
+};
+
+#[derive(Debug, Clone, Getters)]
+pub struct AdaptiveStepController<T: Real> {
+    #[getter(copy)]
+    absolute_tolerance: T,
+    #[getter(copy)]
+    relative_tolerance: T,
+    #[getter(copy)]
+    min_step: T,
+    #[getter(copy)]
+    max_step: T,
+    #[getter(copy)]
+    proposed_step: T,
+    #[getter(copy)]
+    accepted_step: T,
+    #[getter(copy)]
+    error: T,
+    #[getter(copy)]
+    rejected_steps: usize,
+}
+
+impl<T: Real> AdaptiveStepController<T> {
+    // None unless the tolerances are positive and 0 < min_step <= max_step; a zero step would
+    // never reach the end of an interval.
+    pub fn new(
+        absolute_tolerance: T,
+        relative_tolerance: T,
+        min_step: T,
+        max_step: T,
+        initial_step: T,
+    ) -> Option<Self> {
+        let positive = |value: T| value.is_finite() && value > T::zero();
+        if !positive(absolute_tolerance)
+            || !positive(relative_tolerance)
+            || !positive(min_step)
+            || !positive(max_step)
+            || min_step > max_step
+        {
+            return None;
+        }
+
+        Some(Self {
+            absolute_tolerance,
+            relative_tolerance,
+            min_step,
+            max_step,
+            proposed_step: initial_step.clamp(min_step, max_step),
+            accepted_step: T::zero(),
+            error: T::zero(),
+            rejected_steps: 0,
+        })
+    }
+
+    pub fn evaluate(
+        &mut self,
+        previous: &TopState<T>,
+        next: &TopState<T>,
+        error: &ErrorEstimate<T>,
+        h: T,
+        order: i32,
+    ) -> bool {
+        let scaled = |e: T, a: T, b: T| {
+            e / (self.absolute_tolerance + self.relative_tolerance * a.abs().max(b.abs()))
+        };
+
+        let sum = error
+            .q
+            .iter()
+            .zip(previous.q.coords.iter().zip(next.q.coords.iter()))
+            .chain(error.w.iter().zip(previous.w.iter().zip(next.w.iter())))
+            .fold(T::zero(), |sum, (e, (a, b))| {
+                sum + scaled(*e, *a, *b).powi(2)
+            });
+        let norm = (sum / real(7.0)).sqrt();
+
+        let factor = if norm.is_finite() {
+            (real::<T>(0.9) * norm.powf(real(-1.0 / (order + 1) as f64)))
+                .clamp(real(0.2), real(5.0))
+        } else {
+            real(0.2)
+        };
+        let accepted = norm <= T::one() || h <= self.min_step;
+
+        self.error = norm;
+        if accepted {
+            self.accepted_step = h;
+            if h >= self.proposed_step {
+                self.proposed_step = (h * factor).clamp(self.min_step, self.max_step);
+            }
+        } else {
+            self.rejected_steps += 1;
+            self.proposed_step = (h * factor).clamp(self.min_step, self.max_step);
+        }
+
+        accepted
+    }
+
+    pub fn cast<U: Real>(&self) -> AdaptiveStepController<U> {
+        AdaptiveStepController {
+            absolute_tolerance: self.absolute_tolerance.cast(),
+            relative_tolerance: self.relative_tolerance.cast(),
+            min_step: self.min_step.cast(),
+            max_step: self.max_step.cast(),
+            proposed_step: self.proposed_step.cast(),
+            accepted_step: self.accepted_step.cast(),
+            error: self.error.cast(),
+            rejected_steps: self.rejected_steps,
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    body_definition::BodyDefinition,
+    config::{read, Config, ConfigError},
+    headless::{run_headless, HeadlessSettings, RunSummary},
+    integrator::IntegratorKind,
+    shape::Shape,
+};
+
+// A set of runs: every combination of the listed values, on top of `base`. An empty list
+// keeps the base value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sweep {
+    pub end_time: f64,
+    pub energy_tolerance: Option<f64>,
+    pub cube_size: Vec<f64>,
+    pub density: Vec<f64>,
+    pub deviation: Vec<f64>,
+    pub angular_velocity: Vec<f64>,
+    pub integration_step: Vec<f64>,
+    pub gravity: Vec<bool>,
+    pub integrator: Vec<IntegratorKind>,
+    pub base: Config,
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            end_time: 10.0,
+            energy_tolerance: None,
+            cube_size: Vec::new(),
+            density: Vec::new(),
+            deviation: Vec::new(),
+            angular_velocity: Vec::new(),
+            integration_step: Vec::new(),
+            gravity: Vec::new(),
+            integrator: Vec::new(),
+            base: Config::default(),
+        }
+    }
+}
+
+// The swept values of one run, None where the base value is kept.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct SweepPoint {
+    pub cube_size: Option<f64>,
+    pub density: Option<f64>,
+    pub deviation: Option<f64>,
+    pub angular_velocity: Option<f64>,
+    pub integration_step: Option<f64>,
+    pub gravity: Option<bool>,
+    pub integrator: Option<IntegratorKind>,
+}
+
+pub struct SweepResult {
+    pub point: SweepPoint,
+    pub summary: RunSummary,
+}
+
+impl Sweep {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let sweep: Self = read(path)?;
+
+        let cube = matches!(
+            sweep.base.body,
+            BodyDefinition::Shape {
+                shape: Shape::Cube { .. },
+                ..
+            }
+        );
+        let shape = matches!(sweep.base.body, BodyDefinition::Shape { .. });
+        if !sweep.cube_size.is_empty() && !cube {
+            return Err(ConfigError::Invalid(
+                "sweeping cube_size needs a cube body".to_string(),
+            ));
+        }
+        if !sweep.density.is_empty() && !shape {
+            return Err(ConfigError::Invalid(
+                "sweeping density needs a shape body".to_string(),
+            ));
+        }
+        if sweep
+            .energy_tolerance
+            .is_some_and(|tolerance| !(tolerance.is_finite() && tolerance > 0.0))
+        {
+            return Err(ConfigError::Invalid(
+                "the energy tolerance must be positive".to_string(),
+            ));
+        }
+        let finite = sweep
+            .deviation
+            .iter()
+            .chain(&sweep.angular_velocity)
+            .all(|value| value.is_finite());
+        if !finite {
+            return Err(ConfigError::Invalid(
+                "the deviations and angular velocities must be finite".to_string(),
+            ));
+        }
+        // A body whose inertia cannot be inverted would panic inside the thread pool and take
+        // every other run with it, and a step that never advances would hold the sweep up for
+        // good, so each combination is checked up front.
+        for point in sweep.points() {
+            sweep.config(&point).validate()?;
+        }
+
+        Ok(sweep)
+    }
+
+    pub fn points(&self) -> Vec<SweepPoint> {
+        let points = vec![SweepPoint::default()];
+        let points = expand(points, &self.cube_size, |point, value| {
+            point.cube_size = Some(value)
+        });
+        let points = expand(points, &self.density, |point, value| {
+            point.density = Some(value)
+        });
+        let points = expand(points, &self.deviation, |point, value| {
+            point.deviation = Some(value)
+        });
+        let points = expand(points, &self.angular_velocity, |point, value| {
+            point.angular_velocity = Some(value)
+        });
+        let points = expand(points, &self.integration_step, |point, value| {
+            point.integration_step = Some(value)
+        });
+        let points = expand(points, &self.gravity, |point, value| {
+            point.gravity = Some(value)
+        });
+
+        expand(points, &self.integrator, |point, value| {
+            point.integrator = Some(value)
+        })
+    }
+
+    pub fn config(&self, point: &SweepPoint) -> Config {
+        let mut config = self.base.clone();
+
+        if let BodyDefinition::Shape { shape, density, .. } = &mut config.body {
+            if let (Shape::Cube { size }, Some(value)) = (shape, point.cube_size) {
+                *size = value;
+            }
+            *density = point.density.unwrap_or(*density);
+        }
+        if let Some(deviation) = point.deviation {
+            config.set_deviation(deviation);
+        }
+        if let Some(angular_velocity) = point.angular_velocity {
+            config.set_spin(angular_velocity);
+        }
+        config.integration_step = point.integration_step.unwrap_or(config.integration_step);
+        config.gravity = point.gravity.unwrap_or(config.gravity);
+        config.integrator = point.integrator.unwrap_or(config.integrator);
+        config.end_time = Some(self.end_time);
+
+        config
+    }
+
+    // Runs every point in parallel on the rayon pool; `progress` is called with the number
+    // of finished runs as they complete.
+    pub fn run(&self, progress: impl Fn(usize) + Sync) -> Vec<SweepResult> {
+        // Only the summary is kept, so the history holds just the initial state.
+        let settings = HeadlessSettings::new(self.end_time, u64::MAX, self.energy_tolerance);
+        let finished = AtomicUsize::new(0);
+
+        self.points()
+            .into_par_iter()
+            .map(|point| {
+                let (_, summary) =
+                    run_headless(&self.config(&point).simulation_parameters(), &settings);
+                progress(finished.fetch_add(1, Ordering::Relaxed) + 1);
+
+                SweepResult { point, summary }
+            })
+            .collect()
+    }
+
+    // One row per run with the swept values and its metrics; empty cells for metrics that do
+    // not apply, e.g. the fall time of a top that stayed up.
+    pub fn write_csv(&self, path: impl AsRef<Path>, results: &[SweepResult]) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let mut names = vec!["run"];
+        let swept = [
+            ("cube_size", !self.cube_size.is_empty()),
+            ("density", !self.density.is_empty()),
+            ("deviation", !self.deviation.is_empty()),
+            ("angular_velocity", !self.angular_velocity.is_empty()),
+            ("integration_step", !self.integration_step.is_empty()),
+            ("gravity", !self.gravity.is_empty()),
+            ("integrator", !self.integrator.is_empty()),
+        ];
+        names.extend(
+            swept
+                .iter()
+                .filter(|(_, swept)| *swept)
+                .map(|(name, _)| name),
+        );
+        names.extend([
+            "max_tilt",
+            "fall_time",
+            "max_energy_drift",
+            "precession_period",
+            "divergence",
+        ]);
+        writeln!(writer, "{}", names.join(","))?;
+
+        let optional =
+            |value: Option<f64>| value.map_or_else(String::new, |value| value.to_string());
+        for (index, result) in results.iter().enumerate() {
+            let point = &result.point;
+            let summary = &result.summary;
+            let values = [
+                optional(point.cube_size),
+                optional(point.density),
+                optional(point.deviation),
+                optional(point.angular_velocity),
+                optional(point.integration_step),
+                point
+                    .gravity
+                    .map_or_else(String::new, |value| value.to_string()),
+                point
+                    .integrator
+                    .map_or_else(String::new, |value| format!("{:?}", value)),
+            ];
+
+            let mut row = vec![index.to_string()];
+            row.extend(
+                values
+                    .into_iter()
+                    .zip(swept)
+                    .filter(|(_, (_, swept))| *swept)
+                    .map(|(value, _)| value),
+            );
+            row.extend([
+                summary.max_tilt.to_string(),
+                optional(summary.fall_time),
+                summary.max_energy_drift.to_string(),
+                optional(summary.precession_period),
+                summary
+                    .divergence
+                    .map_or_else(String::new, |divergence| format!("{:?}", divergence)),
+            ]);
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        writer.flush()
+    }
+}
+
+// Each point once with each of `values`, or unchanged when there are none.
+fn expand<V: Copy>(
+    points: Vec<SweepPoint>,
+    values: &[V],
+    set: impl Fn(&mut SweepPoint, V),
+) -> Vec<SweepPoint> {
+    if values.is_empty() {
+        return points;
+    }
+
+    points
+        .iter()
+        .flat_map(|point| {
+            values.iter().map(|&value| {
+                let mut point = *point;
+                set(&mut point, value);
+                point
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,72 @@
+use nalgebra::{Matrix3, Quaternion, UnitQuaternion, Vector3};
+
+use crate::{
+    diagnostics::Diagnostics,
+    real::{real, Real},
+    rigid_body::RigidBody,
+    simulation::TopState,
+};
+
+#[derive(Debug, Clone)]
+pub struct TopDynamics<T: Real> {
+    moment_of_interia: Matrix3<T>,
+    inversed_moment_of_interia: Matrix3<T>,
+    center: Vector3<T>,
+    weight: T,
+    gravity: bool,
+}
+
+impl<T: Real> TopDynamics<T> {
+    pub fn new(body: &RigidBody<T>) -> Self {
+        let moment_of_interia = body.moment_of_interia();
+
+        Self {
+            moment_of_interia,
+            inversed_moment_of_interia: moment_of_interia.try_inverse().unwrap(),
+            center: body.center_of_mass(),
+            weight: body.mass(),
+            gravity: true,
+        }
+    }
+
+    pub fn gravity(&self) -> bool {
+        self.gravity
+    }
+
+    pub fn set_gravity(&mut self, gravity: bool) {
+        self.gravity = gravity;
+    }
+
+    pub fn diagnostics(&self, state: &TopState<T>, quaternion_norm: T) -> Diagnostics<T> {
+        let angular_momentum = state.q.to_rotation_matrix() * (self.moment_of_interia * state.w);
+        let potential_energy = if self.gravity {
+            self.weight * real(9.81) * (state.q.to_rotation_matrix() * self.center).y
+        } else {
+            T::zero()
+        };
+
+        Diagnostics::new(
+            state.w.dot(&(self.moment_of_interia * state.w)) / real(2.0),
+            potential_energy,
+            angular_momentum.y,
+            angular_momentum.norm(),
+            quaternion_norm - T::one(),
+        )
+    }
+
+    pub fn angular_acceleration(&self, q: &UnitQuaternion<T>, w: &Vector3<T>) -> Vector3<T> {
+        let f = if self.gravity {
+            Vector3::new(T::zero(), -self.weight * real(9.81), T::zero())
+        } else {
+            Vector3::zeros()
+        };
+
+        self.inversed_moment_of_interia
+            * (self.center.cross(&(q.to_rotation_matrix().transpose() * f))
+                + (self.moment_of_interia * w).cross(w))
+    }
+
+    pub fn quaternion_derivative(q: &Quaternion<T>, w: &Vector3<T>) -> Quaternion<T> {
+        q * Quaternion::new(T::zero(), w.x, w.y, w.z) / real(2.0)
+    }
+}
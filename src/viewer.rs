@@ -0,0 +1,882 @@
+use std::thread::JoinHandle;
+
+use chrono::Local;
+use egui::{Color32, DragValue, Slider, ViewportId, Widget};
+use egui_plot::{Line, Plot, PlotPoints};
+use glium::{Blend, Surface};
+use nalgebra::Matrix4;
+use winit::event::{self, ElementState, MouseButton};
+
+use crate::{
+    angular_velocity_drawer::AngularVelocityDrawer,
+    body_editor::BodyEditor,
+    config::Config,
+    cuber_drawer::CubeDrawer,
+    diagonal_drawer::DiagonalDrawer,
+    export_window::ExportWindow,
+    gravity_vector_drawer::GravityVectorDrawer,
+    infinite_grid_drawer::InfiniteGridDrawer,
+    initial_conditions_editor::initial_conditions_ui,
+    integrator::IntegratorKind,
+    mesh_drawer::MeshDrawer,
+    real::Precision,
+    recording::Recording,
+    simulation_clock::ClockMode,
+    simulation_parameters::SimulationParameters,
+    simulation_state::SimulationState,
+    simulation_thread::{spawn_simulation, SharedSimulation},
+    stability::StabilityAnalysis,
+    stability_map_window::StabilityMapWindow,
+    trajectory::Trajectory,
+    trajectory_drawer::TrajectoryDrawer,
+};
+
+// Opens the viewer window and runs its event loop until the window is closed.
+// `config_path` fills the panel's configuration file field.
+pub fn run(mut config: Config, mut config_path: String) {
+    let width = 1600;
+    let height = 1200;
+
+    let event_loop = winit::event_loop::EventLoopBuilder::new().build().unwrap();
+    let (window, display) = glium::backend::glutin::SimpleWindowBuilder::new()
+        .with_title("Spinning top")
+        .with_inner_size(width, height)
+        .build(&event_loop);
+
+    let mut egui_glium =
+        egui_glium::EguiGlium::new(ViewportId::ROOT, &display, &window, &event_loop);
+
+    let drawing_parameters = glium::DrawParameters {
+        depth: glium::Depth {
+            test: glium::draw_parameters::DepthTest::IfLess,
+            write: false,
+            ..Default::default()
+        },
+        backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+        blend: Blend::alpha_blending(),
+        ..Default::default()
+    };
+
+    let mut perspective = Matrix4::new_perspective(
+        width as f32 / height as f32,
+        std::f32::consts::PI / 2.0,
+        0.1,
+        100.0,
+    );
+
+    let mut mouse_position = (0.0, 0.0);
+    let mut camera_move_button_pressed = false;
+
+    let infinite_grid_drawer = InfiniteGridDrawer::new(&display);
+
+    let mut body = config.body.build::<f32>();
+    let mut body_editor = BodyEditor::default();
+
+    let cube_drawer = CubeDrawer::new(&display);
+    let mut mesh_drawer = body
+        .shape()
+        .mesh()
+        .map(|mesh| MeshDrawer::new(&display, &mesh));
+    let diagonal_drawer = DiagonalDrawer::new(&display);
+    let gravity_vector_drawer = GravityVectorDrawer::new(&display);
+
+    let angular_velocity_drawer = AngularVelocityDrawer::new(&display);
+
+    let mut step_count = 100u64;
+    let mut scrub: Option<f64> = None;
+    let mut playing = false;
+    let mut run_parameters: Option<SimulationParameters> = None;
+    let mut recording_path = String::new();
+    let mut recording_message: Option<Result<String, String>> = None;
+    let mut config_message: Option<Result<String, String>> = None;
+
+    let shared = SharedSimulation::new();
+    *shared.gravity.lock().unwrap() = config.gravity;
+    *shared.clock_mode.lock().unwrap() = config.clock_mode;
+    *shared.rotation.lock().unwrap() = config.initial.orientation.rotation();
+    shared
+        .history
+        .lock()
+        .unwrap()
+        .set_decimation(config.history_decimation);
+    let mut simulation_thread: Option<JoinHandle<()>> = None;
+
+    let mut trajectory = Trajectory::new(config.trajectory_size, &display);
+    let trajectory_drawer = TrajectoryDrawer::new(&display);
+
+    let mut stability =
+        StabilityAnalysis::new(&config.body.build(), &config.initial, config.gravity);
+    let mut stability_map_window = StabilityMapWindow::default();
+    let mut export_window = ExportWindow::default();
+
+    let mut previous_time = Local::now();
+
+    let _ = event_loop.run(move |event, window_target| {
+        let mut redraw = || {
+            let current_time = Local::now();
+            let duration = current_time - previous_time;
+            let duration_in_seconds = duration.num_microseconds().unwrap_or(1) as f64 / 1_000_000.0;
+            let fps = 1.0 / duration_in_seconds;
+            previous_time = current_time;
+
+            egui_glium.run(&window, |egui_ctx| {
+                let parameters = config.simulation_parameters();
+
+                egui::Window::new("panel").show(egui_ctx, |ui| {
+                    let state = shared.state();
+                    let mut rebuild_body = false;
+
+                    ui.horizontal(|ui| {
+                        match state {
+                            SimulationState::Idle | SimulationState::Finished => {
+                                if ui.button("Start").clicked() {
+                                    if let Some(thread) = simulation_thread.take() {
+                                        thread.join().unwrap();
+                                        rebuild_body = true;
+                                    }
+
+                                    trajectory.clear();
+                                    scrub = None;
+                                    run_parameters = Some(parameters.clone());
+                                    simulation_thread =
+                                        Some(spawn_simulation(parameters.clone(), shared.clone()));
+                                }
+                            }
+                            SimulationState::Running => {
+                                if ui.button("Pause").clicked() {
+                                    shared.transition(
+                                        &[SimulationState::Running],
+                                        SimulationState::Paused,
+                                    );
+                                }
+                            }
+                            SimulationState::Paused => {
+                                if ui.button("Resume").clicked() {
+                                    shared.transition(
+                                        &[SimulationState::Paused],
+                                        SimulationState::Running,
+                                    );
+                                }
+                            }
+                        }
+
+                        if ui
+                            .add_enabled(state.is_active(), egui::Button::new("Stop"))
+                            .clicked()
+                        {
+                            *shared.state.lock().unwrap() = SimulationState::Idle;
+
+                            if let Some(thread) = simulation_thread.take() {
+                                thread.join().unwrap();
+                            }
+
+                            rebuild_body = true;
+                        }
+
+                        ui.label(state.to_string());
+                    });
+
+                    if state == SimulationState::Paused {
+                        ui.horizontal(|ui| {
+                            if ui.button("Step").clicked() {
+                                *shared.pending_steps.lock().unwrap() += 1;
+                                scrub = None;
+                            }
+
+                            if ui.button(format!("Step {step_count}")).clicked() {
+                                *shared.pending_steps.lock().unwrap() += step_count;
+                                scrub = None;
+                            }
+
+                            DragValue::new(&mut step_count)
+                                .clamp_range(1..=1_000_000)
+                                .ui(ui);
+                        });
+                    }
+
+                    if state == SimulationState::Running {
+                        scrub = None;
+                        playing = false;
+                    }
+
+                    let span = {
+                        let history = shared.history.lock().unwrap();
+                        history.start().zip(history.end())
+                    };
+
+                    // Playback moves along the history in real time.
+                    if let Some((start, end)) = span.filter(|_| playing) {
+                        let t = scrub.filter(|t| *t < end).unwrap_or(start) + duration_in_seconds;
+                        playing = t < end;
+                        scrub = Some(t.min(end));
+                    }
+
+                    let sample = scrub.and_then(|t| shared.history.lock().unwrap().at(t).copied());
+
+                    if let Some((start, end)) = span {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    state != SimulationState::Running,
+                                    egui::Button::new(if playing { "Pause" } else { "Play" }),
+                                )
+                                .clicked()
+                            {
+                                playing = !playing;
+                            }
+
+                            let mut t = scrub.unwrap_or(end);
+                            if ui
+                                .add_enabled(
+                                    state != SimulationState::Running,
+                                    Slider::new(&mut t, start..=end).suffix(" s"),
+                                )
+                                .changed()
+                            {
+                                scrub = Some(t);
+                            }
+
+                            ui.label("timeline");
+                        });
+                    }
+
+                    if let Some(sample) = sample {
+                        ui.horizontal(|ui| {
+                            ui.label(format!(
+                                "t: {:.4} s, ω: ({:.3}, {:.3}, {:.3})",
+                                sample.t, sample.w.x, sample.w.y, sample.w.z
+                            ));
+
+                            // The new run takes the panel's current parameters from this state on.
+                            if ui.button("Branch from here").clicked() {
+                                *shared.state.lock().unwrap() = SimulationState::Idle;
+                                if let Some(thread) = simulation_thread.take() {
+                                    thread.join().unwrap();
+                                }
+                                rebuild_body = true;
+
+                                trajectory.clear();
+                                while shared.trajectory_queue.pop().is_ok() {}
+                                let tip = config.body.build::<f64>().tip();
+                                for earlier in shared.history.lock().unwrap().samples() {
+                                    if earlier.t < sample.t {
+                                        shared
+                                            .trajectory_queue
+                                            .push((earlier.q * tip).cast())
+                                            .unwrap();
+                                    }
+                                }
+
+                                let branch = SimulationParameters {
+                                    start_state: Some(sample),
+                                    ..parameters.clone()
+                                };
+                                run_parameters = Some(branch.clone());
+                                simulation_thread = Some(spawn_simulation(branch, shared.clone()));
+                                scrub = None;
+                                playing = false;
+                            }
+                        });
+                    }
+
+                    ui.horizontal(|ui| {
+                        if DragValue::new(&mut config.history_decimation)
+                            .clamp_range(1..=1000)
+                            .ui(ui)
+                            .changed()
+                        {
+                            shared
+                                .history
+                                .lock()
+                                .unwrap()
+                                .set_decimation(config.history_decimation);
+                        }
+
+                        ui.label("history decimation");
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut recording_path);
+
+                        let saveable = run_parameters.is_some()
+                            && span.is_some()
+                            && state != SimulationState::Running;
+                        if ui
+                            .add_enabled(saveable, egui::Button::new("save recording"))
+                            .clicked()
+                        {
+                            if let Some(run_parameters) = &run_parameters {
+                                recording_message = Some(
+                                    Recording::save(
+                                        &recording_path,
+                                        run_parameters,
+                                        &shared.history.lock().unwrap(),
+                                    )
+                                    .map(|_| format!("saved {}", recording_path))
+                                    .map_err(|error| error.to_string()),
+                                );
+                            }
+                        }
+
+                        if ui.button("export data").clicked() {
+                            export_window.open();
+                        }
+
+                        if ui.button("load recording").clicked() {
+                            match Recording::load(&recording_path) {
+                                Ok(recording) => {
+                                    *shared.state.lock().unwrap() = SimulationState::Idle;
+                                    if let Some(thread) = simulation_thread.take() {
+                                        thread.join().unwrap();
+                                    }
+
+                                    let loaded = recording.parameters;
+                                    config.apply_run(&loaded);
+                                    *shared.gravity.lock().unwrap() = config.gravity;
+                                    rebuild_body = true;
+
+                                    let mut history = recording.history;
+                                    history.set_decimation(config.history_decimation);
+                                    let tip = loaded.body.build::<f64>().tip();
+                                    trajectory.clear();
+                                    while shared.trajectory_queue.pop().is_ok() {}
+                                    for sample in history.samples() {
+                                        shared
+                                            .trajectory_queue
+                                            .push((sample.q * tip).cast())
+                                            .unwrap();
+                                    }
+
+                                    scrub = history.start();
+                                    *shared.history.lock().unwrap() = history;
+                                    run_parameters = Some(loaded);
+                                    playing = false;
+                                    recording_message =
+                                        Some(Ok(format!("loaded {}", recording_path)));
+                                }
+                                Err(error) => recording_message = Some(Err(error.to_string())),
+                            }
+                        }
+                    });
+
+                    match &recording_message {
+                        Some(Ok(message)) => {
+                            ui.label(message);
+                        }
+                        Some(Err(error)) => {
+                            ui.colored_label(Color32::RED, error);
+                        }
+                        None => {}
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut config_path);
+
+                        if ui.button("save config").clicked() {
+                            config_message = Some(
+                                config
+                                    .save(&config_path)
+                                    .map(|_| format!("saved {}", config_path))
+                                    .map_err(|error| error.to_string()),
+                            );
+                        }
+
+                        // A running simulation keeps its parameters, the loaded ones apply
+                        // from the next start.
+                        if ui.button("load config").clicked() {
+                            match Config::load(&config_path) {
+                                Ok(loaded) => {
+                                    config = loaded;
+                                    *shared.gravity.lock().unwrap() = config.gravity;
+                                    *shared.clock_mode.lock().unwrap() = config.clock_mode;
+                                    shared
+                                        .history
+                                        .lock()
+                                        .unwrap()
+                                        .set_decimation(config.history_decimation);
+                                    trajectory.resize(config.trajectory_size, &display);
+                                    if !state.is_active() {
+                                        *shared.rotation.lock().unwrap() =
+                                            config.initial.orientation.rotation();
+                                        rebuild_body = true;
+                                    }
+                                    stability = StabilityAnalysis::new(
+                                        &config.body.build(),
+                                        &config.initial,
+                                        config.gravity,
+                                    );
+                                    config_message = Some(Ok(format!("loaded {}", config_path)));
+                                }
+                                Err(error) => config_message = Some(Err(error.to_string())),
+                            }
+                        }
+                    });
+
+                    match &config_message {
+                        Some(Ok(message)) => {
+                            ui.label(message);
+                        }
+                        Some(Err(error)) => {
+                            ui.colored_label(Color32::RED, error);
+                        }
+                        None => {}
+                    }
+
+                    let body_changed = body_editor.ui(ui, &mut config.body);
+                    rebuild_body |= body_changed && !state.is_active();
+                    if rebuild_body {
+                        body = config.body.build();
+                        mesh_drawer = body
+                            .shape()
+                            .mesh()
+                            .map(|mesh| MeshDrawer::new(&display, &mesh));
+                    }
+
+                    let initial_conditions_changed = initial_conditions_ui(ui, &mut config.initial);
+                    if initial_conditions_changed && !state.is_active() {
+                        *shared.rotation.lock().unwrap() = config.initial.orientation.rotation();
+                    }
+
+                    if body_changed || initial_conditions_changed || rebuild_body {
+                        stability = StabilityAnalysis::new(
+                            &config.body.build(),
+                            &config.initial,
+                            config.gravity,
+                        );
+                    }
+
+                    ui.label(format!(
+                        "sleeping-top critical spin: {}",
+                        stability.critical_spin.map_or_else(
+                            || "-".to_string(),
+                            |critical| format!("{critical:.3} rad/s")
+                        )
+                    ));
+                    if stability.below_critical() {
+                        ui.colored_label(
+                            Color32::YELLOW,
+                            format!(
+                                "spin {:.3} rad/s is below it, an upright top will fall",
+                                stability.spin.abs()
+                            ),
+                        );
+                    }
+                    ui.collapsing("linearised stability", |ui| {
+                        if stability.equilibrium_residual > 1e-9 {
+                            ui.label("the spin axis is not principal, not an equilibrium");
+                        }
+                        ui.label(format!(
+                            "max growth rate: {:.4} 1/s",
+                            stability.growth_rate().max(0.0)
+                        ));
+                        for eigenvalue in &stability.eigenvalues {
+                            ui.label(format!("{:+.4} {:+.4}i", eigenvalue.re, eigenvalue.im));
+                        }
+                    });
+
+                    ui.horizontal(|ui| {
+                        DragValue::new(&mut config.integration_step)
+                            .clamp_range(0.0001..=0.1)
+                            .speed(0.0001)
+                            .ui(ui);
+
+                        ui.label("integration step");
+                    });
+
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("integrator")
+                            .selected_text(config.integrator.to_string())
+                            .show_ui(ui, |ui| {
+                                for kind in IntegratorKind::ALL {
+                                    ui.selectable_value(
+                                        &mut config.integrator,
+                                        kind,
+                                        kind.to_string(),
+                                    );
+                                }
+                            });
+
+                        ui.label("integrator");
+                    });
+
+                    ui.horizontal(|ui| {
+                        for value in Precision::ALL {
+                            ui.radio_value(&mut config.precision, value, value.to_string());
+                        }
+
+                        ui.label("precision");
+                    });
+
+                    ui.add_enabled_ui(config.integrator.is_embedded(), |ui| {
+                        ui.checkbox(&mut config.adaptive_step, "adaptive step");
+                    });
+
+                    if config.adaptive_step && config.integrator.is_embedded() {
+                        ui.horizontal(|ui| {
+                            Slider::new(
+                                &mut config.step_controller.absolute_tolerance,
+                                1e-8..=1e-1,
+                            )
+                            .logarithmic(true)
+                            .ui(ui);
+
+                            ui.label("absolute tolerance");
+                        });
+
+                        ui.horizontal(|ui| {
+                            Slider::new(
+                                &mut config.step_controller.relative_tolerance,
+                                1e-8..=1e-1,
+                            )
+                            .logarithmic(true)
+                            .ui(ui);
+
+                            ui.label("relative tolerance");
+                        });
+
+                        ui.horizontal(|ui| {
+                            Slider::new(&mut config.step_controller.min_step, 1e-8..=1e-2)
+                                .logarithmic(true)
+                                .ui(ui);
+
+                            ui.label("min step");
+                        });
+
+                        ui.horizontal(|ui| {
+                            Slider::new(&mut config.step_controller.max_step, 1e-5..=1e-1)
+                                .logarithmic(true)
+                                .ui(ui);
+
+                            ui.label("max step");
+                        });
+                    }
+
+                    ui.horizontal(|ui| {
+                        let mut finite = config.end_time.is_some();
+                        if ui.checkbox(&mut finite, "finish at").changed() {
+                            config.end_time = finite.then_some(10.0);
+                        }
+
+                        if let Some(end_time) = &mut config.end_time {
+                            DragValue::new(end_time)
+                                .clamp_range(0.001..=100_000.0)
+                                .speed(0.1)
+                                .suffix(" s")
+                                .ui(ui);
+                        }
+                    });
+
+                    let mut clock_changed = false;
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("clock")
+                            .selected_text(config.clock_mode.to_string())
+                            .show_ui(ui, |ui| {
+                                for kind in ClockMode::KINDS {
+                                    if ui
+                                        .selectable_label(
+                                            config.clock_mode.same_kind(&kind),
+                                            kind.to_string(),
+                                        )
+                                        .clicked()
+                                        && !config.clock_mode.same_kind(&kind)
+                                    {
+                                        config.clock_mode = kind;
+                                        clock_changed = true;
+                                    }
+                                }
+                            });
+
+                        match &mut config.clock_mode {
+                            ClockMode::SlowMotion(rate) => {
+                                clock_changed |= Slider::new(rate, 0.01..=1.0)
+                                    .logarithmic(true)
+                                    .text("×")
+                                    .ui(ui)
+                                    .changed();
+                            }
+                            ClockMode::FastForward(rate) => {
+                                clock_changed |= Slider::new(rate, 2.0..=100.0)
+                                    .logarithmic(true)
+                                    .text("×")
+                                    .ui(ui)
+                                    .changed();
+                            }
+                            ClockMode::RealTime | ClockMode::AsFastAsPossible => {}
+                        }
+
+                        ui.label("clock");
+                    });
+                    if clock_changed {
+                        *shared.clock_mode.lock().unwrap() = config.clock_mode;
+                    }
+
+                    if let Some(clock) = shared.clock.lock().unwrap().as_ref() {
+                        ui.label(format!(
+                            "simulated time: {:.3} s, real-time factor: {}",
+                            clock.simulated_time,
+                            clock
+                                .real_time_factor
+                                .map_or_else(|| "-".to_string(), |factor| format!("{factor:.2}×"))
+                        ));
+                    }
+
+                    if let Some(controller) = shared.step_controller.lock().unwrap().as_ref() {
+                        ui.label(format!("accepted step: {:.3e}", controller.accepted_step()));
+                        ui.label(format!("error estimate: {:.3e}", controller.error()));
+                        ui.label(format!("rejected steps: {}", controller.rejected_steps()));
+                    }
+
+                    if let Some((diagnostics, drift)) = shared.diagnostics.lock().unwrap().as_ref()
+                    {
+                        ui.label(format!(
+                            "energy: {:.4} (drift {:.2e})",
+                            diagnostics.total_energy(),
+                            drift.energy
+                        ));
+                        ui.label(format!(
+                            "L·y: {:.4} (drift {:.2e})",
+                            diagnostics.vertical_angular_momentum, drift.vertical_angular_momentum
+                        ));
+                        ui.label(format!(
+                            "|L|: {:.4} (drift {:.2e})",
+                            diagnostics.angular_momentum_norm, drift.angular_momentum_norm
+                        ));
+                        ui.label(format!(
+                            "|q| - 1: {:.2e}",
+                            diagnostics.quaternion_norm_error
+                        ));
+                    }
+
+                    if let Some(readout) = shared.precession.lock().unwrap().as_ref() {
+                        ui.label(format!(
+                            "φ: {:.2}°  θ: {:.2}°  ψ: {:.2}°",
+                            readout.precession.to_degrees(),
+                            readout.nutation.to_degrees(),
+                            readout.spin.to_degrees()
+                        ));
+                        let optional = |value: Option<f64>, unit: &str| {
+                            value.map_or_else(
+                                || "-".to_string(),
+                                |value| format!("{value:.4} {unit}"),
+                            )
+                        };
+                        ui.label(format!(
+                            "precession period: {}",
+                            optional(readout.precession_period, "s")
+                        ));
+                        ui.label(format!(
+                            "precession rate: {} (gyroscopic mgl/(I3ω): {})",
+                            optional(readout.precession_rate, "rad/s"),
+                            optional(readout.gyroscopic_rate, "rad/s")
+                        ));
+                        ui.label(format!(
+                            "nutation amplitude: {}, frequency: {}",
+                            optional(readout.nutation_amplitude.map(f64::to_degrees), "°"),
+                            optional(readout.nutation_frequency, "Hz")
+                        ));
+                    }
+
+                    ui.checkbox(&mut config.compare_to_analytic, "compare to analytic");
+
+                    if ui.button("stability map").clicked() {
+                        stability_map_window.open();
+                    }
+
+                    ui.checkbox(&mut config.draw.body, "draw body");
+                    ui.checkbox(&mut config.draw.diagonal, "draw diagonal");
+                    ui.checkbox(&mut config.draw.trajectory, "draw trajectory");
+                    ui.checkbox(&mut config.draw.gravity_vector, "draw gravity vector");
+                    ui.checkbox(
+                        &mut config.draw.angular_velocity,
+                        "draw initial angular velocity",
+                    );
+
+                    if Slider::new(&mut config.trajectory_size, 10..=1_000_000)
+                        .ui(ui)
+                        .changed()
+                    {
+                        trajectory.resize(config.trajectory_size, &display);
+                    }
+
+                    if ui.checkbox(&mut config.gravity, "gravity").changed() {
+                        *shared.gravity.lock().unwrap() = config.gravity;
+                        stability = StabilityAnalysis::new(
+                            &config.body.build(),
+                            &config.initial,
+                            config.gravity,
+                        );
+                    }
+
+                    ui.label(format!("FPS: {:.1}", fps));
+                });
+
+                stability_map_window.show(egui_ctx, &parameters);
+                export_window.show(egui_ctx, run_parameters.as_ref(), &shared.history);
+
+                if let Some(reference) = shared.analytic_reference.lock().unwrap().as_ref() {
+                    egui::Window::new("analytic comparison").show(egui_ctx, |ui| {
+                        ui.label(reference);
+
+                        let error = shared.analytic_error.lock().unwrap();
+                        if let Some([_, last]) = error.last() {
+                            ui.label(format!("orientation error: {:.3e} rad", last));
+                        }
+
+                        Plot::new("analytic error")
+                            .view_aspect(2.0)
+                            .x_axis_label("t")
+                            .y_axis_label("error [rad]")
+                            .show(ui, |plot_ui| {
+                                plot_ui.line(
+                                    Line::new(PlotPoints::from(error.clone()))
+                                        .name("orientation error"),
+                                )
+                            });
+                    });
+                }
+            });
+
+            window.request_redraw();
+
+            let rotation = scrub
+                .and_then(|t| {
+                    shared
+                        .history
+                        .lock()
+                        .unwrap()
+                        .at(t)
+                        .map(|sample| sample.q.cast())
+                })
+                .unwrap_or_else(|| *shared.rotation.lock().unwrap());
+            body.set_rotation(rotation);
+
+            trajectory.add_points(shared.trajectory_queue.clone());
+
+            let view = config.camera.view();
+            let mut target = display.draw();
+
+            target.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), 1.0);
+
+            if config.draw.body {
+                match &mesh_drawer {
+                    Some(mesh_drawer) => mesh_drawer.draw(
+                        &mut target,
+                        &perspective,
+                        &view,
+                        &body,
+                        &drawing_parameters,
+                    ),
+                    None => cube_drawer.draw(
+                        &mut target,
+                        &perspective,
+                        &view,
+                        &body,
+                        &drawing_parameters,
+                    ),
+                }
+            }
+
+            if config.draw.diagonal {
+                diagonal_drawer.draw(&mut target, &perspective, &view, &body, &drawing_parameters);
+            }
+
+            if config.draw.gravity_vector {
+                gravity_vector_drawer.draw(
+                    &mut target,
+                    &perspective,
+                    &view,
+                    &body,
+                    &drawing_parameters,
+                );
+            }
+
+            if config.draw.angular_velocity && !shared.state().is_active() {
+                angular_velocity_drawer.draw(
+                    &mut target,
+                    &perspective,
+                    &view,
+                    &config.initial.world_angular_velocity(),
+                    &drawing_parameters,
+                );
+            }
+
+            if !trajectory.points().is_empty() && config.draw.trajectory {
+                trajectory_drawer.draw(
+                    &mut target,
+                    &perspective,
+                    &view,
+                    &trajectory,
+                    &drawing_parameters,
+                );
+            }
+
+            infinite_grid_drawer.draw(&mut target, &perspective, &view, &drawing_parameters);
+
+            egui_glium.paint(&display, &mut target);
+
+            target.finish().unwrap();
+        };
+
+        match event {
+            event::Event::WindowEvent { event, .. } => {
+                use event::WindowEvent;
+                match &event {
+                    WindowEvent::RedrawRequested => redraw(),
+                    WindowEvent::CloseRequested | WindowEvent::Destroyed => {
+                        window_target.exit();
+                    }
+                    WindowEvent::Resized(new_size) => {
+                        display.resize((*new_size).into());
+                        perspective = Matrix4::new_perspective(
+                            new_size.width as f32 / new_size.height as f32,
+                            std::f32::consts::PI / 2.0,
+                            0.1,
+                            100.0,
+                        );
+                    }
+                    WindowEvent::CursorMoved { position, .. } => {
+                        let delta = (position.x - mouse_position.0, position.y - mouse_position.1);
+                        mouse_position = (position.x, position.y);
+                        if camera_move_button_pressed {
+                            config.camera.rotate(delta.0 as f32, delta.1 as f32);
+                        }
+                    }
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        if *button == MouseButton::Middle {
+                            camera_move_button_pressed = *state == ElementState::Pressed;
+                        }
+                    }
+                    WindowEvent::KeyboardInput {
+                        device_id: _,
+                        event,
+                        is_synthetic: _,
+                    } => {
+                        if event.logical_key == "c" && event.state.is_pressed() && !event.repeat {
+                            camera_move_button_pressed = !camera_move_button_pressed;
+                        }
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => match delta {
+                        event::MouseScrollDelta::LineDelta(_x, y) => {
+                            config.camera.zoom(-y * 0.1);
+                        }
+                        _ => {}
+                    },
+                    WindowEvent::TouchpadMagnify { delta, .. } => {
+                        config.camera.zoom(-*delta as f32 * 3.0);
+                    }
+                    _ => {}
+                }
+
+                let event_response = egui_glium.on_event(&window, &event);
+
+                if event_response.repaint {
+                    window.request_redraw();
+                }
+            }
+            event::Event::NewEvents(event::StartCause::ResumeTimeReached { .. }) => {
+                window.request_redraw();
+            }
+            _ => (),
+        }
+    });
+}